@@ -0,0 +1,575 @@
+use crate::environment::Resolver;
+use crate::hmac_signature::{HmacConfig, SignatureEncoding, TEMPLATE_HELP};
+use crate::oauth2::{now_secs, GrantType, OAuth2Config, OAuth2Token};
+use crate::params::{encode_strict, split_url, KeyValue};
+use crate::sigv4::SigV4Config;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use eframe::egui;
+use egui::{Color32, RichText, Ui};
+use percent_encoding::percent_decode_str;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ApiKeyLocation {
+    #[default]
+    Header,
+    Query,
+}
+
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Auth {
+    // 요청은 그룹의 인증을 상속, 그룹에서는 None과 같다
+    #[default]
+    Inherit,
+    None,
+    Basic {
+        username: String,
+        password: String,
+    },
+    Bearer {
+        token: String,
+    },
+    ApiKey {
+        key: String,
+        value: String,
+        location: ApiKeyLocation,
+    },
+    OAuth2(Box<OAuth2Config>),
+    // 아래 방식들은 전송 시점에 처리하므로 apply에서는 아무것도 하지 않는다
+    AwsSigV4(SigV4Config),
+    Digest {
+        username: String,
+        password: String,
+    },
+    Hmac(HmacConfig),
+}
+
+#[derive(Default)]
+pub struct AuthEditResponse {
+    pub changed: bool,
+    pub get_new_token: bool,
+}
+
+impl Auth {
+    fn label(&self) -> &'static str {
+        match self {
+            Auth::Inherit => "Inherit from Group",
+            Auth::None => "No Auth",
+            Auth::Basic { .. } => "Basic Auth",
+            Auth::Bearer { .. } => "Bearer Token",
+            Auth::ApiKey { .. } => "API Key",
+            Auth::OAuth2(_) => "OAuth 2.0",
+            Auth::AwsSigV4(_) => "AWS Signature V4",
+            Auth::Digest { .. } => "Digest Auth",
+            Auth::Hmac(_) => "HMAC Signature",
+        }
+    }
+
+    fn variants(allow_inherit: bool) -> Vec<Auth> {
+        let mut variants = vec![
+            Auth::None,
+            Auth::Basic {
+                username: String::new(),
+                password: String::new(),
+            },
+            Auth::Bearer {
+                token: String::new(),
+            },
+            Auth::ApiKey {
+                key: String::new(),
+                value: String::new(),
+                location: ApiKeyLocation::Header,
+            },
+            Auth::OAuth2(Box::default()),
+            Auth::AwsSigV4(SigV4Config::default()),
+            Auth::Digest {
+                username: String::new(),
+                password: String::new(),
+            },
+            Auth::Hmac(HmacConfig::default()),
+        ];
+        if allow_inherit {
+            variants.insert(0, Auth::Inherit);
+        }
+        variants
+    }
+
+    pub fn resolve(&self, resolver: &mut Resolver) -> Auth {
+        match self {
+            Auth::Inherit => Auth::Inherit,
+            Auth::None => Auth::None,
+            Auth::Basic { username, password } => Auth::Basic {
+                username: resolver.resolve(username),
+                password: resolver.resolve(password),
+            },
+            Auth::Bearer { token } => Auth::Bearer {
+                token: resolver.resolve(token),
+            },
+            Auth::ApiKey {
+                key,
+                value,
+                location,
+            } => Auth::ApiKey {
+                key: resolver.resolve(key),
+                value: resolver.resolve(value),
+                location: *location,
+            },
+            Auth::OAuth2(config) => Auth::OAuth2(Box::new(OAuth2Config {
+                grant_type: config.grant_type,
+                token_url: resolver.resolve(&config.token_url),
+                auth_url: resolver.resolve(&config.auth_url),
+                client_id: resolver.resolve(&config.client_id),
+                client_secret: resolver.resolve(&config.client_secret),
+                username: resolver.resolve(&config.username),
+                password: resolver.resolve(&config.password),
+                scope: resolver.resolve(&config.scope),
+                redirect_port: config.redirect_port,
+                credentials_in_body: config.credentials_in_body,
+                token: config.token.clone(),
+            })),
+            Auth::AwsSigV4(config) => Auth::AwsSigV4(SigV4Config {
+                access_key: resolver.resolve(&config.access_key),
+                secret_key: resolver.resolve(&config.secret_key),
+                session_token: resolver.resolve(&config.session_token),
+                region: resolver.resolve(&config.region),
+                service: resolver.resolve(&config.service),
+            }),
+            Auth::Digest { username, password } => Auth::Digest {
+                username: resolver.resolve(username),
+                password: resolver.resolve(password),
+            },
+            Auth::Hmac(config) => Auth::Hmac(HmacConfig {
+                secret: resolver.resolve(&config.secret),
+                header_name: resolver.resolve(&config.header_name),
+                timestamp_header: resolver.resolve(&config.timestamp_header),
+                template: config.template.clone(),
+                encoding: config.encoding,
+            }),
+        }
+    }
+
+    // 인증 정보를 헤더 또는 쿼리 파라미터로 적용
+    pub fn apply(&self, headers: &mut Vec<KeyValue>, url: &mut String) {
+        match self {
+            Auth::Inherit
+            | Auth::None
+            | Auth::AwsSigV4(_)
+            | Auth::Digest { .. }
+            | Auth::Hmac(_) => {}
+            Auth::Basic { username, password } => {
+                let credentials = STANDARD.encode(format!("{}:{}", username, password));
+                set_header(headers, "Authorization", format!("Basic {}", credentials));
+            }
+            Auth::Bearer { token } => {
+                set_header(headers, "Authorization", format!("Bearer {}", token));
+            }
+            Auth::ApiKey {
+                key,
+                value,
+                location,
+            } => {
+                if key.is_empty() {
+                    return;
+                }
+                match location {
+                    ApiKeyLocation::Header => set_header(headers, key, value.clone()),
+                    ApiKeyLocation::Query => {
+                        // 같은 이름의 쿼리 파라미터는 API 키로 바꾸고 나머지는 그대로 둔다
+                        let fragment = url.find('#').map(|idx| &url[idx..]).unwrap_or_default();
+                        let (base, query) = split_url(url);
+                        let mut pairs: Vec<&str> = query
+                            .unwrap_or_default()
+                            .split('&')
+                            .filter(|pair| {
+                                let name = pair.split('=').next().unwrap_or_default();
+                                !pair.is_empty()
+                                    && percent_decode_str(name).decode_utf8_lossy() != key.as_str()
+                            })
+                            .collect();
+                        let pair = format!("{}={}", encode_strict(key), encode_strict(value));
+                        pairs.push(&pair);
+                        *url = format!("{}?{}{}", base, pairs.join("&"), fragment);
+                    }
+                }
+            }
+            Auth::OAuth2(config) => {
+                if let Some(token) = &config.token {
+                    set_header(headers, "Authorization", token.authorization_value());
+                }
+            }
+        }
+    }
+}
+
+// 같은 이름(대소문자 무시)의 헤더를 교체
+pub fn set_header(headers: &mut Vec<KeyValue>, name: &str, value: String) {
+    headers.retain(|header| !header.key.eq_ignore_ascii_case(name));
+    headers.push(KeyValue::new(name.to_string(), value));
+}
+
+// 인증 편집 UI
+pub fn render_auth(ui: &mut Ui, auth: &mut Auth, allow_inherit: bool) -> AuthEditResponse {
+    let mut changed = false;
+    let mut get_new_token = false;
+    let selected = if !allow_inherit && *auth == Auth::Inherit {
+        Auth::None.label()
+    } else {
+        auth.label()
+    };
+
+    egui::ComboBox::from_label("Type")
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            for variant in Auth::variants(allow_inherit) {
+                let is_selected = variant.label() == auth.label();
+                if ui.selectable_label(is_selected, variant.label()).clicked() && !is_selected {
+                    *auth = variant;
+                    changed = true;
+                }
+            }
+        });
+
+    match auth {
+        Auth::Inherit => {
+            ui.label("Uses the authorization configured on the group");
+        }
+        Auth::None => {}
+        Auth::Basic { username, password } | Auth::Digest { username, password } => {
+            egui::Grid::new("basic_auth").num_columns(2).show(ui, |ui| {
+                ui.label("Username");
+                changed |= ui.text_edit_singleline(username).changed();
+                ui.end_row();
+                ui.label("Password");
+                changed |= ui
+                    .add(egui::TextEdit::singleline(password).password(true))
+                    .changed();
+                ui.end_row();
+            });
+        }
+        Auth::Bearer { token } => {
+            ui.horizontal(|ui| {
+                ui.label("Token");
+                changed |= ui.text_edit_singleline(token).changed();
+            });
+        }
+        Auth::ApiKey {
+            key,
+            value,
+            location,
+        } => {
+            egui::Grid::new("api_key_auth")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Key");
+                    changed |= ui.text_edit_singleline(key).changed();
+                    ui.end_row();
+                    ui.label("Value");
+                    changed |= ui.text_edit_singleline(value).changed();
+                    ui.end_row();
+                    ui.label("Add to");
+                    ui.horizontal(|ui| {
+                        changed |= ui
+                            .selectable_value(location, ApiKeyLocation::Header, "Header")
+                            .changed();
+                        changed |= ui
+                            .selectable_value(location, ApiKeyLocation::Query, "Query Params")
+                            .changed();
+                    });
+                    ui.end_row();
+                });
+        }
+        Auth::OAuth2(config) => {
+            egui::ComboBox::from_label("Grant Type")
+                .selected_text(config.grant_type.label())
+                .show_ui(ui, |ui| {
+                    for grant_type in GrantType::ALL {
+                        changed |= ui
+                            .selectable_value(
+                                &mut config.grant_type,
+                                grant_type,
+                                grant_type.label(),
+                            )
+                            .changed();
+                    }
+                });
+
+            egui::Grid::new("oauth2_auth")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    if config.grant_type == GrantType::AuthorizationCode {
+                        ui.label("Auth URL");
+                        changed |= ui.text_edit_singleline(&mut config.auth_url).changed();
+                        ui.end_row();
+                    }
+                    ui.label("Token URL");
+                    changed |= ui.text_edit_singleline(&mut config.token_url).changed();
+                    ui.end_row();
+                    ui.label("Client ID");
+                    changed |= ui.text_edit_singleline(&mut config.client_id).changed();
+                    ui.end_row();
+                    ui.label("Client Secret");
+                    changed |= ui
+                        .add(egui::TextEdit::singleline(&mut config.client_secret).password(true))
+                        .changed();
+                    ui.end_row();
+                    if config.grant_type == GrantType::Password {
+                        ui.label("Username");
+                        changed |= ui.text_edit_singleline(&mut config.username).changed();
+                        ui.end_row();
+                        ui.label("Password");
+                        changed |= ui
+                            .add(egui::TextEdit::singleline(&mut config.password).password(true))
+                            .changed();
+                        ui.end_row();
+                    }
+                    ui.label("Scope");
+                    changed |= ui.text_edit_singleline(&mut config.scope).changed();
+                    ui.end_row();
+                    if config.grant_type == GrantType::AuthorizationCode {
+                        ui.label("Redirect Port");
+                        changed |= ui
+                            .add(egui::DragValue::new(&mut config.redirect_port))
+                            .on_hover_text("0 picks a free port")
+                            .changed();
+                        ui.end_row();
+                    }
+                });
+            changed |= ui
+                .checkbox(
+                    &mut config.credentials_in_body,
+                    "Send client credentials in body",
+                )
+                .changed();
+
+            ui.horizontal(|ui| {
+                if ui.button("Get New Access Token").clicked() {
+                    get_new_token = true;
+                }
+                if config.token.is_some() && ui.button("Clear Token").clicked() {
+                    config.token = None;
+                    changed = true;
+                }
+            });
+
+            if let Some(token) = &config.token {
+                render_token(ui, token);
+            }
+        }
+        Auth::AwsSigV4(config) => {
+            egui::Grid::new("sigv4_auth").num_columns(2).show(ui, |ui| {
+                ui.label("Access Key");
+                changed |= ui.text_edit_singleline(&mut config.access_key).changed();
+                ui.end_row();
+                ui.label("Secret Key");
+                changed |= ui
+                    .add(egui::TextEdit::singleline(&mut config.secret_key).password(true))
+                    .changed();
+                ui.end_row();
+                ui.label("Session Token");
+                changed |= ui
+                    .add(egui::TextEdit::singleline(&mut config.session_token).password(true))
+                    .changed();
+                ui.end_row();
+                ui.label("Region");
+                changed |= ui.text_edit_singleline(&mut config.region).changed();
+                ui.end_row();
+                ui.label("Service");
+                changed |= ui.text_edit_singleline(&mut config.service).changed();
+                ui.end_row();
+            });
+        }
+        Auth::Hmac(config) => {
+            egui::Grid::new("hmac_auth").num_columns(2).show(ui, |ui| {
+                ui.label("Secret");
+                changed |= ui
+                    .add(egui::TextEdit::singleline(&mut config.secret).password(true))
+                    .changed();
+                ui.end_row();
+                ui.label("Signature Header");
+                changed |= ui.text_edit_singleline(&mut config.header_name).changed();
+                ui.end_row();
+                ui.label("Timestamp Header");
+                changed |= ui
+                    .text_edit_singleline(&mut config.timestamp_header)
+                    .changed();
+                ui.end_row();
+                ui.label("Encoding");
+                ui.horizontal(|ui| {
+                    changed |= ui
+                        .selectable_value(&mut config.encoding, SignatureEncoding::Hex, "Hex")
+                        .changed();
+                    changed |= ui
+                        .selectable_value(&mut config.encoding, SignatureEncoding::Base64, "Base64")
+                        .changed();
+                });
+                ui.end_row();
+            });
+            ui.label("String to Sign");
+            changed |= ui.text_edit_multiline(&mut config.template).changed();
+            ui.label(RichText::new(TEMPLATE_HELP).small());
+        }
+    }
+
+    AuthEditResponse {
+        changed,
+        get_new_token,
+    }
+}
+
+fn render_token(ui: &mut Ui, token: &OAuth2Token) {
+    ui.separator();
+    egui::Grid::new("oauth2_token")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Token Type");
+            ui.label(&token.token_type);
+            ui.end_row();
+            ui.label("Expires");
+            match token.expires_at {
+                Some(expires_at) if expires_at > now_secs() => {
+                    ui.label(format!("in {}s", expires_at - now_secs()));
+                }
+                Some(_) => {
+                    ui.label(RichText::new("expired").color(Color32::RED));
+                }
+                None => {
+                    ui.label("unknown");
+                }
+            }
+            ui.end_row();
+            ui.label("Refresh Token");
+            ui.label(if token.refresh_token.is_some() {
+                "available"
+            } else {
+                "none"
+            });
+            ui.end_row();
+            if let Some(scope) = &token.scope {
+                ui.label("Scope");
+                ui.label(scope);
+                ui.end_row();
+            }
+        });
+
+    ui.collapsing("Access Token", |ui| {
+        ui.add(egui::Label::new(&token.access_token).wrap());
+        if let Some((header, payload)) = token.decode_jwt() {
+            ui.label("Header");
+            ui.monospace(serde_json::to_string_pretty(&header).unwrap_or_default());
+            ui.label("Payload");
+            ui.monospace(serde_json::to_string_pretty(&payload).unwrap_or_default());
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(auth: &Auth, url: &str) -> (Vec<(String, String)>, String) {
+        let mut headers = vec![KeyValue::new(
+            "authorization".to_string(),
+            "old".to_string(),
+        )];
+        let mut url = url.to_string();
+        auth.apply(&mut headers, &mut url);
+        let headers = headers
+            .into_iter()
+            .map(|header| (header.key, header.value))
+            .collect();
+        (headers, url)
+    }
+
+    fn api_key(key: &str, value: &str, location: ApiKeyLocation) -> Auth {
+        Auth::ApiKey {
+            key: key.to_string(),
+            value: value.to_string(),
+            location,
+        }
+    }
+
+    #[test]
+    fn basic_and_bearer_replace_authorization() {
+        let basic = Auth::Basic {
+            username: "Aladdin".to_string(),
+            password: "open sesame".to_string(),
+        };
+        let (headers, url) = apply(&basic, "https://a.test/");
+        assert_eq!(
+            headers,
+            vec![(
+                "Authorization".to_string(),
+                "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==".to_string()
+            )]
+        );
+        assert_eq!(url, "https://a.test/");
+
+        let bearer = Auth::Bearer {
+            token: "abc".to_string(),
+        };
+        assert_eq!(
+            apply(&bearer, "https://a.test/").0,
+            vec![("Authorization".to_string(), "Bearer abc".to_string())]
+        );
+
+        // 인증 없음은 기존 헤더를 건드리지 않는다
+        assert_eq!(apply(&Auth::None, "https://a.test/").0[0].1, "old");
+    }
+
+    #[test]
+    fn api_key_in_header() {
+        let (headers, url) = apply(
+            &api_key("X-Api-Key", "k1", ApiKeyLocation::Header),
+            "https://a.test/",
+        );
+        assert_eq!(headers[1], ("X-Api-Key".to_string(), "k1".to_string()));
+        assert_eq!(url, "https://a.test/");
+
+        // 키 이름이 비어 있으면 적용하지 않는다
+        let (headers, _) = apply(
+            &api_key("", "k1", ApiKeyLocation::Header),
+            "https://a.test/",
+        );
+        assert_eq!(headers.len(), 1);
+    }
+
+    #[test]
+    fn api_key_in_query_is_encoded_and_replaces_existing() {
+        let auth = api_key("api key", "a+b/c=", ApiKeyLocation::Query);
+        let (headers, url) = apply(&auth, "https://a.test/p");
+        assert_eq!(url, "https://a.test/p?api%20key=a%2Bb%2Fc%3D");
+        assert_eq!(headers.len(), 1);
+
+        let auth = api_key("key", "new", ApiKeyLocation::Query);
+        let (_, url) = apply(&auth, "https://a.test/p?a=1&key=old&b=%20#top");
+        assert_eq!(url, "https://a.test/p?a=1&b=%20&key=new#top");
+
+        // 편집 중인 상대 경로에도 붙인다
+        let (_, url) = apply(&auth, "/p?key");
+        assert_eq!(url, "/p?key=new");
+    }
+
+    #[test]
+    fn resolve_substitutes_variables() {
+        let variables = [("user", "alice"), ("token", "t1")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let mut resolver = Resolver::new(&variables);
+        let auth = Auth::Basic {
+            username: "{{user}}".to_string(),
+            password: "{{missing}}".to_string(),
+        };
+        assert!(
+            auth.resolve(&mut resolver)
+                == Auth::Basic {
+                    username: "alice".to_string(),
+                    password: "{{missing}}".to_string(),
+                }
+        );
+        assert!(Auth::Inherit.resolve(&mut resolver) == Auth::Inherit);
+        assert!(resolver.finish().is_err());
+    }
+}
@@ -0,0 +1,353 @@
+use crate::params::KeyValue;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use reqwest::multipart::{Form, Part};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum BodyType {
+    None,
+    // 이전 버전은 항상 JSON으로 보냈으므로 기본값으로 둔다
+    #[default]
+    Json,
+    Text,
+    Xml,
+    FormUrlEncoded,
+    FormData,
+    Binary,
+}
+
+impl BodyType {
+    pub const ALL: [BodyType; 7] = [
+        BodyType::None,
+        BodyType::Json,
+        BodyType::Text,
+        BodyType::Xml,
+        BodyType::FormUrlEncoded,
+        BodyType::FormData,
+        BodyType::Binary,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BodyType::None => "none",
+            BodyType::Json => "JSON",
+            BodyType::Text => "Text",
+            BodyType::Xml => "XML",
+            BodyType::FormUrlEncoded => "x-www-form-urlencoded",
+            BodyType::FormData => "form-data",
+            BodyType::Binary => "binary",
+        }
+    }
+
+    // 사용자가 Content-Type 헤더를 직접 지정하지 않았을 때 사용할 값.
+    // form-data는 boundary가 필요하므로 reqwest가 채운다
+    pub fn content_type(&self) -> Option<&'static str> {
+        match self {
+            BodyType::None => None,
+            BodyType::Json => Some("application/json"),
+            BodyType::Text => Some("text/plain"),
+            BodyType::Xml => Some("application/xml"),
+            BodyType::FormUrlEncoded => Some("application/x-www-form-urlencoded"),
+            BodyType::FormData => None,
+            BodyType::Binary => Some("application/octet-stream"),
+        }
+    }
+}
+
+// 전송할 바디. 파일을 참조하는 바디는 보낼 때 디스크에서 읽는다
+pub enum RequestBody {
+    Bytes(Vec<u8>),
+    FormData(Vec<FormPart>),
+    File(String),
+}
+
+impl RequestBody {
+    pub fn is_empty(&self) -> bool {
+        match self {
+            RequestBody::Bytes(bytes) => bytes.is_empty(),
+            RequestBody::FormData(_) | RequestBody::File(_) => false,
+        }
+    }
+
+    // 서명과 Digest 계산에 쓰는 바디 내용. 파일은 이때만 통째로 읽는다.
+    // multipart는 보내는 바이트와 같아야 하므로 encode_multipart로 먼저 Bytes로 바꿔야 한다
+    pub async fn contents(&self) -> Result<Vec<u8>, String> {
+        match self {
+            RequestBody::Bytes(bytes) => Ok(bytes.clone()),
+            RequestBody::FormData(_) => {
+                Err("Multipart body must be encoded before signing".to_string())
+            }
+            RequestBody::File(path) => tokio::fs::read(path)
+                .await
+                .map_err(|e| format!("Failed to read '{}': {}", path, e)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum FormPartKind {
+    #[default]
+    Text,
+    File,
+}
+
+// multipart/form-data의 한 파트. 파일은 경로만 저장하고 보낼 때 읽는다
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FormPart {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub key: String,
+    #[serde(default)]
+    pub kind: FormPartKind,
+    #[serde(default)]
+    pub value: String,
+    #[serde(default)]
+    pub file_path: String,
+    #[serde(default)]
+    pub content_type: String,
+    #[serde(default)]
+    pub file_name: String,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Default for FormPart {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            key: String::new(),
+            kind: FormPartKind::Text,
+            value: String::new(),
+            file_path: String::new(),
+            content_type: String::new(),
+            file_name: String::new(),
+        }
+    }
+}
+
+// 파트의 내용과 파일 이름. 파일은 이 시점에 디스크에서 읽는다
+async fn read_part(part: &FormPart) -> Result<(Vec<u8>, Option<String>), String> {
+    match part.kind {
+        FormPartKind::Text => Ok((
+            part.value.clone().into_bytes(),
+            Some(part.file_name.clone()).filter(|name| !name.is_empty()),
+        )),
+        FormPartKind::File => {
+            let data = tokio::fs::read(&part.file_path)
+                .await
+                .map_err(|e| format!("Failed to read '{}': {}", part.file_path, e))?;
+            let file_name = if part.file_name.is_empty() {
+                Path::new(&part.file_path)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            } else {
+                part.file_name.clone()
+            };
+            Ok((data, Some(file_name)))
+        }
+    }
+}
+
+// 활성화된 파트로 multipart 폼을 만든다
+pub async fn build_form(parts: &[FormPart]) -> Result<Form, String> {
+    let mut form = Form::new();
+    for part in parts
+        .iter()
+        .filter(|part| part.enabled && !part.key.is_empty())
+    {
+        let (data, file_name) = read_part(part).await?;
+        let mut field = match part.kind {
+            FormPartKind::Text => Part::text(String::from_utf8_lossy(&data).into_owned()),
+            FormPartKind::File => Part::bytes(data),
+        };
+        if let Some(file_name) = file_name {
+            field = field.file_name(file_name);
+        }
+        if !part.content_type.is_empty() {
+            field = field
+                .mime_str(&part.content_type)
+                .map_err(|e| format!("Invalid content type '{}': {}", part.content_type, e))?;
+        }
+        form = form.part(part.key.clone(), field);
+    }
+    Ok(form)
+}
+
+// 서명할 바디가 보내는 바이트와 같도록 multipart 바디를 직접 직렬화한다.
+// (바디, boundary가 든 Content-Type)을 반환한다
+pub async fn encode_multipart(parts: &[FormPart]) -> Result<(Vec<u8>, String), String> {
+    let boundary: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect();
+    // 헤더 값 안의 따옴표와 줄바꿈은 reqwest처럼 퍼센트 인코딩한다
+    let quote = |text: &str| {
+        text.replace('"', "%22")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    };
+
+    let mut body = Vec::new();
+    for part in parts
+        .iter()
+        .filter(|part| part.enabled && !part.key.is_empty())
+    {
+        let (data, file_name) = read_part(part).await?;
+        body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+        let mut disposition = format!(
+            "Content-Disposition: form-data; name=\"{}\"",
+            quote(&part.key)
+        );
+        if let Some(file_name) = &file_name {
+            disposition.push_str(&format!("; filename=\"{}\"", quote(file_name)));
+        }
+        body.extend_from_slice(disposition.as_bytes());
+        body.extend_from_slice(b"\r\n");
+        if !part.content_type.is_empty() {
+            body.extend_from_slice(format!("Content-Type: {}\r\n", part.content_type).as_bytes());
+        } else if part.kind == FormPartKind::File {
+            body.extend_from_slice(b"Content-Type: application/octet-stream\r\n");
+        }
+        body.extend_from_slice(b"\r\n");
+        body.extend_from_slice(&data);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+    Ok((body, format!("multipart/form-data; boundary={}", boundary)))
+}
+
+// application/x-www-form-urlencoded에서 그대로 두는 문자
+const FORM_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'*');
+
+// application/x-www-form-urlencoded 규칙으로 인코딩. 공백은 '+'가 된다
+fn encode_form_component(input: &str, output: &mut String) {
+    for (idx, piece) in input.split(' ').enumerate() {
+        if idx > 0 {
+            output.push('+');
+        }
+        output.extend(utf8_percent_encode(piece, FORM_COMPONENT));
+    }
+}
+
+pub fn encode_form(pairs: &[(String, String)]) -> String {
+    let mut output = String::new();
+    for (idx, (key, value)) in pairs.iter().enumerate() {
+        if idx > 0 {
+            output.push('&');
+        }
+        encode_form_component(key, &mut output);
+        output.push('=');
+        encode_form_component(value, &mut output);
+    }
+    output
+}
+
+// 표를 원문 편집용 텍스트로 바꾼다. {{변수}}는 보낼 때 치환되도록 그대로 둔다
+pub fn form_table_to_text(items: &[KeyValue]) -> String {
+    let encode = |text: &str, output: &mut String| {
+        let mut rest = text;
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}") else {
+                break;
+            };
+            encode_form_component(&rest[..start], output);
+            output.push_str(&rest[start..start + end + 2]);
+            rest = &rest[start + end + 2..];
+        }
+        encode_form_component(rest, output);
+    };
+
+    let mut output = String::new();
+    for item in items
+        .iter()
+        .filter(|item| item.enabled && !item.key.is_empty())
+    {
+        if !output.is_empty() {
+            output.push('&');
+        }
+        encode(&item.key, &mut output);
+        output.push('=');
+        encode(&item.value, &mut output);
+    }
+    output
+}
+
+pub fn decode_form(text: &str) -> Vec<(String, String)> {
+    let decode = |text: &str| {
+        percent_decode_str(&text.replace('+', " "))
+            .decode_utf8_lossy()
+            .into_owned()
+    };
+    text.trim()
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (decode(key), decode(value)),
+            None => (decode(pair), String::new()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_form_uses_plus_for_spaces() {
+        let pairs = vec![
+            ("q".to_string(), "a b+c".to_string()),
+            ("name".to_string(), "가~*".to_string()),
+        ];
+        assert_eq!(encode_form(&pairs), "q=a+b%2Bc&name=%EA%B0%80%7E*");
+    }
+
+    #[tokio::test]
+    async fn encode_multipart_writes_parts_with_boundary() {
+        let parts = vec![
+            FormPart {
+                key: "title".to_string(),
+                value: "hello".to_string(),
+                ..Default::default()
+            },
+            FormPart {
+                key: "say \"hi\"".to_string(),
+                value: "x".to_string(),
+                file_name: "a.txt".to_string(),
+                content_type: "text/plain".to_string(),
+                ..Default::default()
+            },
+            FormPart {
+                enabled: false,
+                key: "skipped".to_string(),
+                ..Default::default()
+            },
+        ];
+        let (body, content_type) = encode_multipart(&parts).await.unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .unwrap();
+        let expected = format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"title\"\r\n\r\nhello\r\n\
+             --{b}\r\nContent-Disposition: form-data; name=\"say %22hi%22\"; filename=\"a.txt\"\r\n\
+             Content-Type: text/plain\r\n\r\nx\r\n--{b}--\r\n",
+            b = boundary
+        );
+        assert_eq!(String::from_utf8(body).unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn contents_rejects_unencoded_form_data() {
+        assert!(RequestBody::FormData(Vec::new()).contents().await.is_err());
+    }
+}
@@ -0,0 +1,800 @@
+use crate::auth::Auth;
+use crate::body::{decode_form, encode_form, BodyType, FormPart, FormPartKind};
+use crate::params::{sync_params_from_url, sync_path_params, KeyValue};
+use crate::sigv4::SigV4Config;
+use crate::ApiRequest;
+
+// 값을 하나 받는 옵션 (curl 8 기준). 여기에 없는 옵션은 값 없는 플래그로 본다.
+// 값을 받는 옵션이 빠지면 그 값이 URL로 잘못 읽히므로 지원하지 않는 옵션도 모두 적어 둔다
+const VALUE_OPTIONS: &[&str] = &[
+    "-X",
+    "--request",
+    "-H",
+    "--header",
+    "-d",
+    "--data",
+    "--data-ascii",
+    "--data-raw",
+    "--data-binary",
+    "--data-urlencode",
+    "--json",
+    "-F",
+    "--form",
+    "--form-string",
+    "-u",
+    "--user",
+    "--url",
+    "-A",
+    "--user-agent",
+    "-e",
+    "--referer",
+    "-b",
+    "--cookie",
+    "-T",
+    "--upload-file",
+    "--aws-sigv4",
+    "-o",
+    "--output",
+    "-m",
+    "--max-time",
+    "--connect-timeout",
+    "-x",
+    "--proxy",
+    "--cacert",
+    "--cert",
+    "--key",
+    "-w",
+    "--write-out",
+    "-c",
+    "--cookie-jar",
+    "--abstract-unix-socket",
+    "--alt-svc",
+    "--capath",
+    "-E",
+    "--cert-type",
+    "--ciphers",
+    "-K",
+    "--config",
+    "--connect-to",
+    "-C",
+    "--continue-at",
+    "--create-file-mode",
+    "--crlfile",
+    "--curves",
+    "--delegation",
+    "--dns-interface",
+    "--dns-ipv4-addr",
+    "--dns-ipv6-addr",
+    "--dns-servers",
+    "--doh-url",
+    "-D",
+    "--dump-header",
+    "--egd-file",
+    "--engine",
+    "--etag-compare",
+    "--etag-save",
+    "--expect100-timeout",
+    "--ftp-account",
+    "--ftp-alternative-to-user",
+    "--ftp-method",
+    "-P",
+    "--ftp-port",
+    "--ftp-ssl-ccc-mode",
+    "--happy-eyeballs-timeout-ms",
+    "--haproxy-clientip",
+    "--hostpubmd5",
+    "--hostpubsha256",
+    "--hsts",
+    "--interface",
+    "--ip-tos",
+    "--ipfs-gateway",
+    "--keepalive-time",
+    "--key-type",
+    "--krb",
+    "--libcurl",
+    "--limit-rate",
+    "--local-port",
+    "--login-options",
+    "--mail-auth",
+    "--mail-from",
+    "--mail-rcpt",
+    "--max-filesize",
+    "--max-redirs",
+    "--netrc-file",
+    "--noproxy",
+    "--oauth2-bearer",
+    "--output-dir",
+    "--parallel-max",
+    "--pass",
+    "--pinnedpubkey",
+    "--preproxy",
+    "--proto",
+    "--proto-default",
+    "--proto-redir",
+    "--proxy-cacert",
+    "--proxy-capath",
+    "--proxy-cert",
+    "--proxy-cert-type",
+    "--proxy-ciphers",
+    "--proxy-crlfile",
+    "--proxy-header",
+    "--proxy-key",
+    "--proxy-key-type",
+    "--proxy-pass",
+    "--proxy-pinnedpubkey",
+    "--proxy-service-name",
+    "--proxy-tls13-ciphers",
+    "--proxy-tlsauthtype",
+    "--proxy-tlspassword",
+    "--proxy-tlsuser",
+    "-U",
+    "--proxy-user",
+    "--proxy1.0",
+    "--pubkey",
+    "-Q",
+    "--quote",
+    "--random-file",
+    "-r",
+    "--range",
+    "--rate",
+    "--request-target",
+    "--resolve",
+    "--retry",
+    "--retry-delay",
+    "--retry-max-time",
+    "--sasl-authzid",
+    "--service-name",
+    "--socks4",
+    "--socks4a",
+    "--socks5",
+    "--socks5-gssapi-service",
+    "--socks5-hostname",
+    "-Y",
+    "--speed-limit",
+    "-y",
+    "--speed-time",
+    "--stderr",
+    "-t",
+    "--telnet-option",
+    "--tftp-blksize",
+    "-z",
+    "--time-cond",
+    "--tls-max",
+    "--tls13-ciphers",
+    "--tlsauthtype",
+    "--tlspassword",
+    "--tlsuser",
+    "--trace",
+    "--trace-ascii",
+    "--trace-config",
+    "--unix-socket",
+    "--url-query",
+    "--variable",
+];
+// 결과에 영향을 주지 않거나 Ruquest가 항상 그렇게 동작하는 플래그
+const IGNORED_FLAGS: [&str; 14] = [
+    "--compressed",
+    "-s",
+    "--silent",
+    "-S",
+    "--show-error",
+    "-v",
+    "--verbose",
+    "-i",
+    "--include",
+    "-L",
+    "--location",
+    "--http1.1",
+    "--http2",
+    "--basic",
+];
+
+// 셸 명령줄을 단어로 나눈다. 작은따옴표, 큰따옴표, $'...', 백슬래시 이스케이프와
+// 줄 끝의 \ (줄 이어쓰기)를 bash와 같은 방식으로 처리한다
+fn tokenize(command: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(next) => {
+                    word.push(next);
+                    in_word = true;
+                }
+                None => {}
+            },
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("Unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err("Unterminated double quote".to_string()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("Unterminated double quote".to_string()),
+                    }
+                }
+            }
+            // 브라우저 개발자 도구가 쓰는 ANSI-C 인용
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some('\\') => ansi_escape(&mut chars, &mut word)?,
+                        Some(c) => word.push(c),
+                        None => return Err("Unterminated $'...' quote".to_string()),
+                    }
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+type Chars<'a> = std::iter::Peekable<std::str::Chars<'a>>;
+
+// \xHH, \uHHHH처럼 최대 max자리의 16진수 코드를 읽는다
+fn hex_char(chars: &mut Chars, max: usize) -> Option<char> {
+    let mut digits = String::new();
+    while digits.len() < max {
+        match chars.peek() {
+            Some(c) if c.is_ascii_hexdigit() => digits.push(*c),
+            _ => break,
+        }
+        chars.next();
+    }
+    u32::from_str_radix(&digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+}
+
+fn ansi_escape(chars: &mut Chars, word: &mut String) -> Result<(), String> {
+    match chars.next() {
+        Some('n') => word.push('\n'),
+        Some('r') => word.push('\r'),
+        Some('t') => word.push('\t'),
+        Some('0') => word.push('\0'),
+        Some('e' | 'E') => word.push('\x1b'),
+        Some('x') => word.extend(hex_char(chars, 2)),
+        Some('u') => word.extend(hex_char(chars, 4)),
+        Some('U') => word.extend(hex_char(chars, 8)),
+        Some(c) => word.push(c),
+        None => return Err("Unterminated $'...' quote".to_string()),
+    }
+    Ok(())
+}
+
+// curl 명령을 요청으로 바꾼다. 무시하거나 지원하지 않는 옵션은 경고로 돌려준다
+pub fn parse(command: &str) -> Result<(ApiRequest, Vec<String>), String> {
+    let words = tokenize(command.trim())?;
+    let mut words = words.into_iter();
+    if words.next().as_deref() != Some("curl") {
+        return Err("Command must start with 'curl'".to_string());
+    }
+
+    // -XPOST, -sSL 같은 붙여 쓴 짧은 옵션을 펼친다
+    let mut args: Vec<(String, Option<String>)> = Vec::new();
+    let mut positional = Vec::new();
+    while let Some(word) = words.next() {
+        if word.starts_with("--") {
+            let value = if VALUE_OPTIONS.contains(&word.as_str()) {
+                Some(words.next().ok_or(format!("Missing value for {}", word))?)
+            } else {
+                None
+            };
+            args.push((word, value));
+        } else if word.len() > 1 && word.starts_with('-') {
+            let letters: Vec<char> = word[1..].chars().collect();
+            for (idx, letter) in letters.iter().enumerate() {
+                let option = format!("-{}", letter);
+                if VALUE_OPTIONS.contains(&option.as_str()) {
+                    let rest: String = letters[idx + 1..].iter().collect();
+                    let value = if rest.is_empty() {
+                        words
+                            .next()
+                            .ok_or(format!("Missing value for {}", option))?
+                    } else {
+                        rest
+                    };
+                    args.push((option, Some(value)));
+                    break;
+                }
+                args.push((option, None));
+            }
+        } else {
+            positional.push(word);
+        }
+    }
+
+    let mut req = ApiRequest::default();
+    let mut warnings = Vec::new();
+    let mut method = None;
+    let mut data: Vec<String> = Vec::new();
+    let mut data_file = None;
+    let mut user = None;
+    let mut digest = false;
+    let mut sigv4 = None;
+    let mut get = false;
+    let mut head = false;
+    let mut json = false;
+
+    for (option, value) in args {
+        let value = value.unwrap_or_default();
+        match option.as_str() {
+            "-X" | "--request" => method = Some(value),
+            "-H" | "--header" => {
+                // "Name;"은 빈 값을 가진 헤더, "Name:"은 curl 기본 헤더를 지우는 문법이다
+                if let Some((name, header)) = value.split_once(':') {
+                    if !header.trim().is_empty() {
+                        req.headers.push(KeyValue::new(
+                            name.trim().to_string(),
+                            header.trim().to_string(),
+                        ));
+                    }
+                } else if let Some(name) = value.strip_suffix(';') {
+                    req.headers
+                        .push(KeyValue::new(name.trim().to_string(), String::new()));
+                } else {
+                    warnings.push(format!("Invalid header '{}' was ignored", value));
+                }
+            }
+            "-d" | "--data" | "--data-ascii" | "--data-binary" => match value.strip_prefix('@') {
+                Some(path) => data_file = Some(path.to_string()),
+                // --data-binary 외에는 curl이 줄바꿈을 지우고 보낸다
+                None if option == "--data-binary" => data.push(value),
+                None => data.push(value.replace(['\r', '\n'], "")),
+            },
+            "--data-raw" => data.push(value),
+            "--json" => {
+                json = true;
+                match value.strip_prefix('@') {
+                    Some(path) => data_file = Some(path.to_string()),
+                    None => data.push(value),
+                }
+            }
+            "--data-urlencode" => {
+                // "name=content"는 content만, "content"와 "=content"는 전체를 인코딩한다
+                let encoded = match value.split_once('=') {
+                    Some(("", content)) => encode_form(&[(content.to_string(), String::new())])
+                        .trim_end_matches('=')
+                        .to_string(),
+                    Some((name, content)) => {
+                        encode_form(&[(name.to_string(), content.to_string())])
+                    }
+                    None => encode_form(&[(value.clone(), String::new())])
+                        .trim_end_matches('=')
+                        .to_string(),
+                };
+                data.push(encoded);
+            }
+            "-F" | "--form" | "--form-string" => match value.split_once('=') {
+                Some((key, content)) => {
+                    req.form_data
+                        .push(form_part(key, content, option == "--form-string"));
+                }
+                None => warnings.push(format!("Invalid form field '{}' was ignored", value)),
+            },
+            "-u" | "--user" => user = Some(value),
+            "--digest" => digest = true,
+            "--aws-sigv4" => sigv4 = Some(value),
+            "--url" => positional.push(value),
+            "-A" | "--user-agent" => req
+                .headers
+                .push(KeyValue::new("User-Agent".to_string(), value)),
+            "-e" | "--referer" => req
+                .headers
+                .push(KeyValue::new("Referer".to_string(), value)),
+            "-b" | "--cookie" => {
+                if value.contains('=') {
+                    req.headers.push(KeyValue::new("Cookie".to_string(), value));
+                } else {
+                    warnings.push(format!("Cookie file '{}' was ignored", value));
+                }
+            }
+            "-T" | "--upload-file" => {
+                data_file = Some(value);
+                method.get_or_insert_with(|| "PUT".to_string());
+            }
+            "-G" | "--get" => get = true,
+            "-I" | "--head" => head = true,
+            "-k" | "--insecure" => req.insecure = true,
+            option if IGNORED_FLAGS.contains(&option) => {}
+            option => warnings.push(format!("Unsupported option '{}' was ignored", option)),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let url = positional
+        .next()
+        .ok_or("No URL found in the curl command")?;
+    for extra in positional {
+        warnings.push(format!("Extra URL '{}' was ignored", extra));
+    }
+    // curl처럼 스킴이 없으면 http로 본다
+    req.url = if url.contains("://") {
+        url
+    } else {
+        format!("http://{}", url)
+    };
+
+    let data = data.join("&");
+    if get && !data.is_empty() {
+        req.url.push(if req.url.contains('?') { '&' } else { '?' });
+        req.url.push_str(&data);
+    }
+    req.method = match method {
+        Some(method) => method.to_uppercase(),
+        None if head => "HEAD".to_string(),
+        None if !get && (!data.is_empty() || data_file.is_some() || !req.form_data.is_empty()) => {
+            "POST".to_string()
+        }
+        None => "GET".to_string(),
+    };
+
+    if json {
+        add_default_header(&mut req.headers, "Content-Type", "application/json");
+        add_default_header(&mut req.headers, "Accept", "application/json");
+    }
+    let content_type = req
+        .headers
+        .iter()
+        .find(|header| header.key.eq_ignore_ascii_case("Content-Type"))
+        .map(|header| header.value.to_lowercase());
+    if !req.form_data.is_empty() {
+        req.body_type = BodyType::FormData;
+        // boundary는 보낼 때 새로 만들어지므로 기존 헤더는 쓰지 않는다
+        req.headers
+            .retain(|header| !header.key.eq_ignore_ascii_case("Content-Type"));
+    } else if let Some(path) = data_file {
+        req.body_type = BodyType::Binary;
+        req.binary_path = path;
+    } else if get || data.is_empty() {
+        req.body_type = BodyType::None;
+    } else {
+        req.body_type = match content_type.as_deref() {
+            // -d는 Content-Type이 없으면 폼 인코딩으로 보낸다
+            None => BodyType::FormUrlEncoded,
+            Some(ty) if ty.starts_with("application/x-www-form-urlencoded") => {
+                BodyType::FormUrlEncoded
+            }
+            Some(ty) if ty.contains("json") => BodyType::Json,
+            Some(ty) if ty.contains("xml") => BodyType::Xml,
+            Some(_) => BodyType::Text,
+        };
+        if req.body_type == BodyType::FormUrlEncoded {
+            req.url_encoded = decode_form(&data)
+                .into_iter()
+                .map(|(key, value)| KeyValue::new(key, value))
+                .collect();
+        }
+        req.body = data;
+    }
+    // 바디 종류에서 정해지는 Content-Type과 같으면 헤더는 빼 둔다
+    if let Some(implied) = req.body_type.content_type() {
+        req.headers.retain(|header| {
+            !header.key.eq_ignore_ascii_case("Content-Type")
+                || !header.value.eq_ignore_ascii_case(implied)
+        });
+    }
+
+    if let Some(user) = user {
+        let (username, password) = match user.split_once(':') {
+            Some((username, password)) => (username.to_string(), password.to_string()),
+            None => (user, String::new()),
+        };
+        req.auth = if let Some(provider) = sigv4 {
+            // --aws-sigv4 "aws:amz:region:service"
+            let parts: Vec<&str> = provider.split(':').collect();
+            Auth::AwsSigV4(SigV4Config {
+                access_key: username,
+                secret_key: password,
+                region: parts.get(2).unwrap_or(&"").to_string(),
+                service: parts.get(3).unwrap_or(&"").to_string(),
+                ..Default::default()
+            })
+        } else if digest {
+            Auth::Digest { username, password }
+        } else {
+            Auth::Basic { username, password }
+        };
+    }
+
+    // 이름은 새 요청 다이얼로그의 기본값으로 쓴다
+    req.name = reqwest::Url::parse(&req.url)
+        .map(|url| match url.path() {
+            "/" => url.host_str().unwrap_or_default().to_string(),
+            path => path.to_string(),
+        })
+        .unwrap_or_default();
+    sync_params_from_url(&req.url, &mut req.params);
+    sync_path_params(&req.url, &mut req.path_params);
+    Ok((req, warnings))
+}
+
+// -F 값. "@경로"는 파일, ";type="과 ";filename="은 파트 속성이다
+fn form_part(key: &str, content: &str, literal: bool) -> FormPart {
+    let mut part = FormPart {
+        key: key.to_string(),
+        ..Default::default()
+    };
+    if literal {
+        part.value = content.to_string();
+        return part;
+    }
+    let mut attributes = content.split(';');
+    let value = attributes.next().unwrap_or_default();
+    for attribute in attributes {
+        match attribute.trim().split_once('=') {
+            Some(("type", ty)) => part.content_type = ty.to_string(),
+            Some(("filename", name)) => part.file_name = name.trim_matches('"').to_string(),
+            _ => {}
+        }
+    }
+    match value.strip_prefix('@') {
+        Some(path) => {
+            part.kind = FormPartKind::File;
+            part.file_path = path.to_string();
+        }
+        None => part.value = value.to_string(),
+    }
+    part
+}
+
+fn add_default_header(headers: &mut Vec<KeyValue>, name: &str, value: &str) {
+    if !headers
+        .iter()
+        .any(|header| header.key.eq_ignore_ascii_case(name))
+    {
+        headers.push(KeyValue::new(name.to_string(), value.to_string()));
+    }
+}
+
+// 셸에서 그대로 쓸 수 있도록 필요할 때만 작은따옴표로 감싼다
+fn quote(word: &str) -> String {
+    let safe = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+%".contains(c));
+    if safe {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+// 변수 치환과 인증 적용이 끝난 요청을 curl 명령으로 만든다. 인자마다 줄을 나눈다
+pub fn to_command(req: &ApiRequest) -> String {
+    let mut args: Vec<String> = Vec::new();
+    let has_body = match req.body_type {
+        BodyType::None => false,
+        BodyType::FormData => !req.form_data.is_empty(),
+        BodyType::Binary => !req.binary_path.is_empty(),
+        _ => !req.body.is_empty(),
+    };
+
+    match req.method.as_str() {
+        // -X HEAD는 curl이 바디를 기다리므로 --head를 쓴다
+        "HEAD" => args.push("--head".to_string()),
+        "GET" if !has_body => {}
+        "POST" if has_body => {}
+        method => args.push(format!("-X {}", quote(method))),
+    }
+    args.push(quote(&req.url));
+
+    for header in req.headers.iter().filter(|header| !header.key.is_empty()) {
+        args.push(if header.value.is_empty() {
+            format!("-H {}", quote(&format!("{};", header.key)))
+        } else {
+            format!("-H {}", quote(&format!("{}: {}", header.key, header.value)))
+        });
+    }
+    let has_content_type = req
+        .headers
+        .iter()
+        .any(|header| header.key.eq_ignore_ascii_case("Content-Type"));
+    if has_body && !has_content_type {
+        if let Some(content_type) = req.body_type.content_type() {
+            args.push(format!(
+                "-H {}",
+                quote(&format!("Content-Type: {}", content_type))
+            ));
+        }
+    }
+
+    match &req.auth {
+        Auth::Digest { username, password } => {
+            args.push("--digest".to_string());
+            args.push(format!(
+                "-u {}",
+                quote(&format!("{}:{}", username, password))
+            ));
+        }
+        Auth::AwsSigV4(config) => {
+            args.push(format!(
+                "--aws-sigv4 {}",
+                quote(&format!("aws:amz:{}:{}", config.region, config.service))
+            ));
+            args.push(format!(
+                "-u {}",
+                quote(&format!("{}:{}", config.access_key, config.secret_key))
+            ));
+            if !config.session_token.is_empty() {
+                args.push(format!(
+                    "-H {}",
+                    quote(&format!("x-amz-security-token: {}", config.session_token))
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    if has_body {
+        match req.body_type {
+            BodyType::FormData => {
+                for part in &req.form_data {
+                    args.push(match part.kind {
+                        FormPartKind::File => {
+                            let mut value = format!("{}=@{}", part.key, part.file_path);
+                            if !part.content_type.is_empty() {
+                                value.push_str(&format!(";type={}", part.content_type));
+                            }
+                            if !part.file_name.is_empty() {
+                                value.push_str(&format!(";filename={}", part.file_name));
+                            }
+                            format!("-F {}", quote(&value))
+                        }
+                        // 값이 @나 <로 시작해도 파일로 해석되지 않게 한다
+                        FormPartKind::Text if part.content_type.is_empty() => format!(
+                            "--form-string {}",
+                            quote(&format!("{}={}", part.key, part.value))
+                        ),
+                        FormPartKind::Text => format!(
+                            "-F {}",
+                            quote(&format!(
+                                "{}={};type={}",
+                                part.key, part.value, part.content_type
+                            ))
+                        ),
+                    });
+                }
+            }
+            BodyType::Binary => args.push(format!(
+                "--data-binary {}",
+                quote(&format!("@{}", req.binary_path))
+            )),
+            _ => args.push(format!("--data-raw {}", quote(&req.body))),
+        }
+    }
+    if req.insecure {
+        args.push("-k".to_string());
+    }
+
+    let mut command = "curl".to_string();
+    for arg in args {
+        command.push_str(" \\\n  ");
+        command.push_str(&arg);
+    }
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_handles_quotes_and_continuations() {
+        let words = tokenize("curl 'a b' \"c \\\"d\\\"\" $'e\\tf' g\\ h \\\n  -k").unwrap();
+        assert_eq!(words, ["curl", "a b", "c \"d\"", "e\tf", "g h", "-k"]);
+        assert!(tokenize("curl 'open").is_err());
+    }
+
+    #[test]
+    fn parse_reads_method_headers_and_json_body() {
+        let (req, warnings) = parse(
+            "curl -sSL -XPATCH https://api.example.com/users/1?x=1 \
+             -H 'Content-Type: application/json' -H 'X-Empty;' \
+             --data-raw '{\"name\":\"a\"}' -u alice:secret -k",
+        )
+        .unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(req.method, "PATCH");
+        assert_eq!(req.url, "https://api.example.com/users/1?x=1");
+        assert_eq!(req.name, "/users/1");
+        assert_eq!(req.body_type, BodyType::Json);
+        assert_eq!(req.body, "{\"name\":\"a\"}");
+        let headers: Vec<(&str, &str)> = req
+            .headers
+            .iter()
+            .map(|h| (h.key.as_str(), h.value.as_str()))
+            .collect();
+        assert_eq!(headers, [("X-Empty", "")]);
+        assert!(matches!(&req.auth, Auth::Basic { username, password }
+            if username == "alice" && password == "secret"));
+        assert!(req.insecure);
+        assert_eq!(req.params[0].key, "x");
+    }
+
+    #[test]
+    fn parse_defaults_data_to_form_post() {
+        let (req, _) = parse("curl example.com/login -d user=a -d 'pass=b c'").unwrap();
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.url, "http://example.com/login");
+        assert_eq!(req.body_type, BodyType::FormUrlEncoded);
+        assert_eq!(req.url_encoded[1].value, "b c");
+    }
+
+    #[test]
+    fn parse_reads_form_parts() {
+        let (req, _) =
+            parse("curl https://x.test/up -F title=hi -F 'file=@/tmp/a.png;type=image/png'")
+                .unwrap();
+        assert_eq!(req.body_type, BodyType::FormData);
+        assert_eq!(req.form_data[0].value, "hi");
+        assert_eq!(req.form_data[1].kind, FormPartKind::File);
+        assert_eq!(req.form_data[1].file_path, "/tmp/a.png");
+        assert_eq!(req.form_data[1].content_type, "image/png");
+    }
+
+    #[test]
+    fn parse_skips_values_of_unsupported_options() {
+        let (req, warnings) =
+            parse("curl --retry 3 --resolve x.test:443:127.0.0.1 --limit-rate 1k https://x.test/a")
+                .unwrap();
+        assert_eq!(req.url, "https://x.test/a");
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].contains("--retry"));
+    }
+
+    #[test]
+    fn to_command_round_trips() {
+        let (req, _) = parse(
+            "curl -X PUT https://x.test/items/1 -H 'X-Id: 7' -H 'Content-Type: application/json' -d '{\"a\":\"it'\\''s\"}'",
+        )
+        .unwrap();
+        let (again, _) = parse(&to_command(&req)).unwrap();
+        assert_eq!(again.method, req.method);
+        assert_eq!(again.url, req.url);
+        assert_eq!(again.body, req.body);
+        assert_eq!(again.body_type, req.body_type);
+        assert_eq!(again.headers[0].key, "X-Id");
+    }
+}
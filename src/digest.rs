@@ -0,0 +1,265 @@
+use crate::encoding::hex;
+use md5::Md5;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+// WWW-Authenticate: Digest ... 헤더에서 읽은 챌린지
+pub struct Challenge {
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: String,
+    qop: Option<String>,
+}
+
+// key="value", key=value 형태의 파라미터 목록을 파싱
+fn parse_params(input: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    loop {
+        while matches!(chars.peek(), Some(c) if *c == ',' || c.is_whitespace()) {
+            chars.next();
+        }
+        let key: String = chars
+            .by_ref()
+            .take_while(|c| *c != '=')
+            .collect::<String>()
+            .trim()
+            .to_ascii_lowercase();
+        if key.is_empty() {
+            break;
+        }
+
+        let mut value = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next()),
+                    '"' => break,
+                    _ => value.push(c),
+                }
+            }
+        } else {
+            while let Some(c) = chars.peek() {
+                if *c == ',' {
+                    break;
+                }
+                value.push(*c);
+                chars.next();
+            }
+            value = value.trim().to_string();
+        }
+        params.push((key, value));
+    }
+
+    params
+}
+
+impl Challenge {
+    pub fn parse(header: &str) -> Option<Challenge> {
+        let header = header.trim();
+        if !header
+            .get(..6)
+            .is_some_and(|scheme| scheme.eq_ignore_ascii_case("digest"))
+        {
+            return None;
+        }
+        let params = parse_params(&header[6..]);
+        let get = |name: &str| {
+            params
+                .iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.clone())
+        };
+
+        // auth-int보다 auth를 우선 사용
+        let qop = get("qop").map(|qop| {
+            let options: Vec<&str> = qop.split(',').map(str::trim).collect();
+            if options.contains(&"auth") || !options.contains(&"auth-int") {
+                "auth".to_string()
+            } else {
+                "auth-int".to_string()
+            }
+        });
+
+        Some(Challenge {
+            realm: get("realm").unwrap_or_default(),
+            nonce: get("nonce")?,
+            opaque: get("opaque"),
+            algorithm: get("algorithm").unwrap_or_else(|| "MD5".to_string()),
+            qop,
+        })
+    }
+
+    fn hash(&self, data: impl AsRef<[u8]>) -> String {
+        let algorithm = self.algorithm.to_ascii_uppercase();
+        if algorithm.starts_with("SHA-256") {
+            hex(&Sha256::digest(data))
+        } else {
+            hex(&Md5::digest(data))
+        }
+    }
+
+    // 챌린지에 대한 Authorization 헤더 값을 만든다
+    pub fn authorization(
+        &self,
+        username: &str,
+        password: &str,
+        method: &str,
+        uri: &str,
+        body: &[u8],
+    ) -> String {
+        let cnonce: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(16)
+            .map(char::from)
+            .collect();
+        self.authorization_with_cnonce(username, password, method, uri, body, &cnonce)
+    }
+
+    fn authorization_with_cnonce(
+        &self,
+        username: &str,
+        password: &str,
+        method: &str,
+        uri: &str,
+        body: &[u8],
+        cnonce: &str,
+    ) -> String {
+        let nc = "00000001";
+
+        let mut ha1 = self.hash(format!("{}:{}:{}", username, self.realm, password));
+        if self.algorithm.to_ascii_lowercase().ends_with("-sess") {
+            ha1 = self.hash(format!("{}:{}:{}", ha1, self.nonce, cnonce));
+        }
+
+        let ha2 = if self.qop.as_deref() == Some("auth-int") {
+            let body_hash = self.hash(body);
+            self.hash(format!("{}:{}:{}", method, uri, body_hash))
+        } else {
+            self.hash(format!("{}:{}", method, uri))
+        };
+
+        let response = match &self.qop {
+            Some(qop) => self.hash(format!(
+                "{}:{}:{}:{}:{}:{}",
+                ha1, self.nonce, nc, cnonce, qop, ha2
+            )),
+            None => self.hash(format!("{}:{}:{}", ha1, self.nonce, ha2)),
+        };
+
+        let mut header = format!(
+            "Digest username=\"{}\", realm=\"{}\", nonce=\"{}\", uri=\"{}\", algorithm={}, response=\"{}\"",
+            username, self.realm, self.nonce, uri, self.algorithm, response
+        );
+        if let Some(qop) = &self.qop {
+            header.push_str(&format!(", qop={}, nc={}, cnonce=\"{}\"", qop, nc, cnonce));
+        }
+        if let Some(opaque) = &self.opaque {
+            header.push_str(&format!(", opaque=\"{}\"", opaque));
+        }
+        header
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 7616 3.9.1 예제
+    const RFC7616_CHALLENGE: &str = "Digest realm=\"http-auth@example.org\", qop=\"auth, auth-int\", algorithm=ALGORITHM, nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\"";
+    const RFC7616_CNONCE: &str = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+
+    fn rfc7616_authorization(algorithm: &str) -> String {
+        let challenge =
+            Challenge::parse(&RFC7616_CHALLENGE.replace("ALGORITHM", algorithm)).unwrap();
+        challenge.authorization_with_cnonce(
+            "Mufasa",
+            "Circle of Life",
+            "GET",
+            "/dir/index.html",
+            b"",
+            RFC7616_CNONCE,
+        )
+    }
+
+    #[test]
+    fn parses_challenge() {
+        let challenge =
+            Challenge::parse(&RFC7616_CHALLENGE.replace("ALGORITHM", "SHA-256")).unwrap();
+        assert_eq!(challenge.realm, "http-auth@example.org");
+        assert_eq!(
+            challenge.nonce,
+            "7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v"
+        );
+        assert_eq!(
+            challenge.opaque.as_deref(),
+            Some("FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS")
+        );
+        assert_eq!(challenge.algorithm, "SHA-256");
+        assert_eq!(challenge.qop.as_deref(), Some("auth"));
+
+        let challenge =
+            Challenge::parse(r#"digest realm="a \"b\"", nonce=n1, qop="auth-int""#).unwrap();
+        assert_eq!(challenge.realm, r#"a "b""#);
+        assert_eq!(challenge.nonce, "n1");
+        assert_eq!(challenge.algorithm, "MD5");
+        assert_eq!(challenge.qop.as_deref(), Some("auth-int"));
+
+        assert!(Challenge::parse("Basic realm=\"x\"").is_none());
+        assert!(Challenge::parse("Digest realm=\"x\"").is_none());
+    }
+
+    #[test]
+    fn rfc7616_md5_response() {
+        let header = rfc7616_authorization("MD5");
+        assert!(
+            header.contains(r#"response="8ca523f5e9506fed4657c9700eebdbec""#),
+            "{}",
+            header
+        );
+        assert!(header.contains(r#"uri="/dir/index.html""#));
+        assert!(header.contains("qop=auth, nc=00000001"));
+        assert!(header.contains(&format!(r#"cnonce="{}""#, RFC7616_CNONCE)));
+        assert!(header.contains(r#"opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#));
+    }
+
+    #[test]
+    fn rfc7616_sha256_response() {
+        let header = rfc7616_authorization("SHA-256");
+        assert!(
+            header.contains(
+                r#"response="753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1""#
+            ),
+            "{}",
+            header
+        );
+        assert!(header.contains("algorithm=SHA-256"));
+    }
+
+    #[test]
+    fn rfc2617_response_without_opaque() {
+        // RFC 2617 3.5 예제
+        let challenge = Challenge::parse(
+            r#"Digest realm="testrealm@host.com", qop="auth,auth-int", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093""#,
+        )
+        .unwrap();
+        let header = challenge.authorization_with_cnonce(
+            "Mufasa",
+            "Circle Of Life",
+            "GET",
+            "/dir/index.html",
+            b"",
+            "0a4f113b",
+        );
+        assert!(
+            header.contains(r#"response="6629fae49393a05397450978507c4ef1""#),
+            "{}",
+            header
+        );
+        assert!(!header.contains("opaque"));
+    }
+}
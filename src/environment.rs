@@ -0,0 +1,217 @@
+use eframe::egui;
+use egui::text::{LayoutJob, TextFormat};
+use egui::{Color32, FontId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+const ENVIRONMENTS_FILE: &str = "saved_environments.json";
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Environment {
+    pub name: String,
+    pub variables: Vec<(String, String)>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct EnvironmentStore {
+    pub environments: Vec<Environment>,
+    pub active: Option<String>,
+}
+
+impl EnvironmentStore {
+    pub fn load() -> Self {
+        if let Ok(data) = fs::read_to_string(ENVIRONMENTS_FILE) {
+            println!("Loading environments from file");
+            serde_json::from_str(&data).unwrap_or_default()
+        } else {
+            println!("No saved environments file found");
+            Self::default()
+        }
+    }
+
+    pub fn save(&self) {
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            if let Err(e) = fs::write(ENVIRONMENTS_FILE, json) {
+                println!("Failed to save environments: {}", e);
+            }
+        }
+    }
+
+    pub fn active_environment(&self) -> Option<&Environment> {
+        let active = self.active.as_ref()?;
+        self.environments.iter().find(|env| &env.name == active)
+    }
+}
+
+// {{name}} 형태의 변수 위치를 (시작, 끝, 이름)으로 반환
+fn placeholders(text: &str) -> Vec<(usize, usize, &str)> {
+    let mut result = Vec::new();
+    let mut offset = 0;
+
+    while let Some(start) = text[offset..].find("{{") {
+        let start = offset + start;
+        match text[start + 2..].find("}}") {
+            Some(len) => {
+                let end = start + 2 + len + 2;
+                // 닫히지 않은 {{ 뒤에 변수가 있으면 가장 가까운 {{부터 변수로 본다
+                let start = start + text[start..end - 2].rfind("{{").unwrap_or(0);
+                result.push((start, end, text[start + 2..end - 2].trim()));
+                offset = end;
+            }
+            None => break,
+        }
+    }
+
+    result
+}
+
+// 변수 치환기. 치환하지 못한 변수 이름을 모아두었다가 finish에서 에러로 반환한다
+pub struct Resolver<'a> {
+    variables: &'a HashMap<String, String>,
+    unresolved: Vec<String>,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn new(variables: &'a HashMap<String, String>) -> Self {
+        Self {
+            variables,
+            unresolved: Vec::new(),
+        }
+    }
+
+    pub fn resolve(&mut self, text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        let mut last = 0;
+
+        for (start, end, name) in placeholders(text) {
+            output.push_str(&text[last..start]);
+            match self.variables.get(name) {
+                Some(value) => output.push_str(value),
+                None => {
+                    if !self.unresolved.iter().any(|n| n == name) {
+                        self.unresolved.push(name.to_string());
+                    }
+                    output.push_str(&text[start..end]);
+                }
+            }
+            last = end;
+        }
+        output.push_str(&text[last..]);
+
+        output
+    }
+
+    pub fn finish(self) -> Result<(), String> {
+        if self.unresolved.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Unresolved variables: {}",
+                self.unresolved.join(", ")
+            ))
+        }
+    }
+}
+
+// 에디터에서 정의된 변수는 초록색, 정의되지 않은 변수는 빨간색으로 표시
+pub fn variable_layout_job(
+    text: &str,
+    variables: &HashMap<String, String>,
+    font_id: FontId,
+    text_color: Color32,
+    wrap_width: f32,
+) -> LayoutJob {
+    let mut job = LayoutJob::default();
+    let plain = TextFormat::simple(font_id.clone(), text_color);
+    let mut last = 0;
+
+    for (start, end, name) in placeholders(text) {
+        job.append(&text[last..start], 0.0, plain.clone());
+        let color = if variables.contains_key(name) {
+            Color32::from_rgb(80, 200, 120)
+        } else {
+            Color32::RED
+        };
+        let mut format = TextFormat::simple(font_id.clone(), color);
+        if color == Color32::RED {
+            format.underline = egui::Stroke::new(1.0, color);
+        }
+        job.append(&text[start..end], 0.0, format);
+        last = end;
+    }
+    job.append(&text[last..], 0.0, plain);
+    job.wrap.max_width = wrap_width;
+
+    job
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variables(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn placeholders_trim_names_and_skip_unclosed() {
+        assert_eq!(
+            placeholders("{{ host }}/a/{{id}}"),
+            vec![(0, 10, "host"), (13, 19, "id")]
+        );
+        assert!(placeholders("{{ not closed").is_empty());
+        assert_eq!(placeholders("{{a}} {{b"), vec![(0, 5, "a")]);
+        assert_eq!(placeholders("{{ x {{y}}"), vec![(5, 10, "y")]);
+    }
+
+    #[test]
+    fn resolve_substitutes_known_and_keeps_unknown() {
+        let vars = variables(&[("host", "api.test"), ("id", "7")]);
+        let mut resolver = Resolver::new(&vars);
+        assert_eq!(
+            resolver.resolve("https://{{host}}/users/{{ id }}?q={{missing}}"),
+            "https://api.test/users/7?q={{missing}}"
+        );
+        assert_eq!(
+            resolver.resolve("{{missing}} {{other}}"),
+            "{{missing}} {{other}}"
+        );
+        assert_eq!(
+            resolver.finish().unwrap_err(),
+            "Unresolved variables: missing, other"
+        );
+
+        let mut resolver = Resolver::new(&vars);
+        assert_eq!(resolver.resolve("{{host}}"), "api.test");
+        assert!(resolver.finish().is_ok());
+    }
+
+    #[test]
+    fn resolve_is_single_pass() {
+        // 값 안의 {{...}}는 다시 치환하지 않는다
+        let vars = variables(&[("outer", "{{inner}}"), ("inner", "x")]);
+        let mut resolver = Resolver::new(&vars);
+        assert_eq!(resolver.resolve("{{outer}}"), "{{inner}}");
+        assert!(resolver.finish().is_ok());
+
+        // 중첩되면 안쪽 변수만 치환하고 바깥 중괄호는 글자로 남긴다
+        let mut resolver = Resolver::new(&vars);
+        assert_eq!(resolver.resolve("{{a{{inner}}}}"), "{{ax}}");
+        assert!(resolver.finish().is_ok());
+    }
+
+    #[test]
+    fn literal_braces_without_closing_pass_through() {
+        let vars = variables(&[("a", "1")]);
+        let mut resolver = Resolver::new(&vars);
+        assert_eq!(
+            resolver.resolve(r#"{"template": "{{ value"} {{a}}"#),
+            r#"{"template": "{{ value"} 1"#
+        );
+        assert!(resolver.finish().is_ok());
+    }
+}
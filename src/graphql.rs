@@ -0,0 +1,533 @@
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+pub const INTROSPECTION_QUERY: &str = r#"query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types {
+      kind
+      name
+      description
+      fields(includeDeprecated: true) {
+        name
+        description
+        args { name type { ...TypeRef } }
+        type { ...TypeRef }
+      }
+      inputFields { name type { ...TypeRef } }
+      enumValues(includeDeprecated: true) { name }
+    }
+  }
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType { kind name ofType { kind name ofType { kind name ofType { kind name } } } }
+}"#;
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphQlRequest {
+    pub query: String,
+    // JSON 객체 텍스트. 비어 있으면 보내지 않는다
+    pub variables: String,
+    // None이면 operationName을 보내지 않는다
+    pub operation_name: Option<String>,
+    #[serde(skip)]
+    pub schema: Option<Schema>,
+}
+
+impl GraphQlRequest {
+    // {"query", "variables", "operationName"} 형태의 요청 바디
+    pub fn body(&self) -> Result<String, String> {
+        let mut body = json!({ "query": self.query });
+        if !self.variables.trim().is_empty() {
+            let variables: Value = serde_json::from_str(&self.variables)
+                .map_err(|e| format!("Invalid GraphQL variables: {}", e))?;
+            body["variables"] = variables;
+        }
+        if let Some(name) = &self.operation_name {
+            body["operationName"] = Value::String(name.clone());
+        }
+        serde_json::to_string_pretty(&body).map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Schema {
+    pub query_type: Option<NamedType>,
+    pub mutation_type: Option<NamedType>,
+    pub subscription_type: Option<NamedType>,
+    pub types: Vec<SchemaType>,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct NamedType {
+    pub name: String,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaType {
+    pub kind: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub fields: Option<Vec<SchemaField>>,
+    #[serde(default)]
+    pub input_fields: Option<Vec<InputValue>>,
+    #[serde(default)]
+    pub enum_values: Option<Vec<NamedType>>,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct SchemaField {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub args: Vec<InputValue>,
+    #[serde(rename = "type")]
+    pub ty: TypeRef,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct InputValue {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: TypeRef,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeRef {
+    pub kind: String,
+    pub name: Option<String>,
+    pub of_type: Option<Box<TypeRef>>,
+}
+
+impl TypeRef {
+    // NON_NULL, LIST를 벗겨낸 실제 타입 이름
+    pub fn named(&self) -> &str {
+        match (&self.name, &self.of_type) {
+            (Some(name), _) => name,
+            (None, Some(inner)) => inner.named(),
+            (None, None) => "",
+        }
+    }
+
+    // `[User!]!` 같은 SDL 표기
+    pub fn display(&self) -> String {
+        let inner = || {
+            self.of_type
+                .as_ref()
+                .map(|inner| inner.display())
+                .unwrap_or_default()
+        };
+        match self.kind.as_str() {
+            "NON_NULL" => format!("{}!", inner()),
+            "LIST" => format!("[{}]", inner()),
+            _ => self.name.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl SchemaField {
+    pub fn signature(&self) -> String {
+        if self.args.is_empty() {
+            format!("{}: {}", self.name, self.ty.display())
+        } else {
+            let args: Vec<String> = self
+                .args
+                .iter()
+                .map(|arg| format!("{}: {}", arg.name, arg.ty.display()))
+                .collect();
+            format!("{}({}): {}", self.name, args.join(", "), self.ty.display())
+        }
+    }
+}
+
+impl Schema {
+    pub fn find_type(&self, name: &str) -> Option<&SchemaType> {
+        self.types
+            .iter()
+            .find(|ty| ty.name.as_deref() == Some(name))
+    }
+
+    pub fn root_types(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("Query", &self.query_type),
+            ("Mutation", &self.mutation_type),
+            ("Subscription", &self.subscription_type),
+        ]
+        .into_iter()
+        .filter_map(|(label, ty)| ty.as_ref().map(|ty| (label, ty.name.as_str())))
+        .collect()
+    }
+
+    fn root_type(&self, operation: &str) -> Option<&str> {
+        let ty = match operation {
+            "mutation" => &self.mutation_type,
+            "subscription" => &self.subscription_type,
+            _ => &self.query_type,
+        };
+        ty.as_ref().map(|ty| ty.name.as_str())
+    }
+
+    // 커서 앞의 선택 집합에서 쓸 수 있는 필드 중 입력 중인 단어로 시작하는 것
+    pub fn completions(&self, query: &str, cursor: usize) -> (usize, Vec<&SchemaField>) {
+        let cursor = cursor.min(query.len());
+        let before = &query[..cursor];
+        let prefix_start = before
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_name_char(*c))
+            .last()
+            .map(|(idx, _)| idx)
+            .unwrap_or(cursor);
+        let prefix = &query[prefix_start..cursor];
+
+        let Some(type_name) = self.selection_type(&query[..prefix_start]) else {
+            return (prefix_start, Vec::new());
+        };
+        let fields = self
+            .find_type(&type_name)
+            .and_then(|ty| ty.fields.as_ref())
+            .map(|fields| {
+                fields
+                    .iter()
+                    .filter(|field| field.name.starts_with(prefix) && field.name.as_str() != prefix)
+                    .collect()
+            })
+            .unwrap_or_default();
+        (prefix_start, fields)
+    }
+
+    // 텍스트 끝이 어느 타입의 선택 집합 안에 있는지 중괄호를 따라가며 찾는다
+    fn selection_type(&self, text: &str) -> Option<String> {
+        let mut stack: Vec<String> = Vec::new();
+        let mut operation = String::from("query");
+        let mut words: Vec<String> = Vec::new();
+        let mut paren_depth = 0;
+        // @include 같은 디렉티브 이름은 필드 이름으로 보지 않는다
+        let mut directive = false;
+
+        for token in tokens(text) {
+            match token {
+                Token::Word(_) if directive => directive = false,
+                Token::Word(word) if paren_depth == 0 => {
+                    if stack.is_empty() && matches!(word, "query" | "mutation" | "subscription") {
+                        operation = word.to_string();
+                    }
+                    words.push(word.to_string());
+                }
+                Token::Word(_) => {}
+                Token::Punct('@') if paren_depth == 0 => directive = true,
+                Token::Punct('(') => paren_depth += 1,
+                Token::Punct(')') => paren_depth -= 1,
+                Token::Punct('{') if paren_depth == 0 => {
+                    let next = match (stack.last(), words.as_slice()) {
+                        // `... on Type {` 또는 `fragment X on Type {`
+                        (_, [.., on, ty]) if on == "on" => ty.clone(),
+                        (None, _) => self.root_type(&operation).unwrap_or_default().to_string(),
+                        (Some(parent), [.., field]) => self
+                            .find_type(parent)
+                            .and_then(|ty| ty.fields.as_ref())
+                            .and_then(|fields| fields.iter().find(|f| &f.name == field))
+                            .map(|field| field.ty.named().to_string())
+                            .unwrap_or_default(),
+                        (Some(_), []) => String::new(),
+                    };
+                    stack.push(next);
+                    words.clear();
+                }
+                Token::Punct('}') if paren_depth == 0 => {
+                    stack.pop();
+                    words.clear();
+                    if stack.is_empty() {
+                        operation = String::from("query");
+                    }
+                }
+                Token::Punct(_) => {}
+            }
+        }
+
+        stack.pop().filter(|name| !name.is_empty())
+    }
+}
+
+enum Token<'a> {
+    Word(&'a str),
+    Punct(char),
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+// 주석과 문자열을 건너뛰고 이름과 구두점만 돌려주는 간단한 토크나이저
+fn tokens(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '#' => while chars.next_if(|(_, c)| *c != '\n').is_some() {},
+            '"' => {
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            c if is_name_char(c) => {
+                let mut end = idx + c.len_utf8();
+                while let Some((next, c)) = chars.next_if(|(_, c)| is_name_char(*c)) {
+                    end = next + c.len_utf8();
+                }
+                tokens.push(Token::Word(&text[idx..end]));
+            }
+            c if c.is_whitespace() || c == ',' => {}
+            c => tokens.push(Token::Punct(c)),
+        }
+    }
+
+    tokens
+}
+
+// 문서에 정의된 이름 있는 operation 목록
+pub fn operation_names(query: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut depth = 0;
+    let mut expect_name = false;
+
+    for token in tokens(query) {
+        match token {
+            Token::Word(word) if depth == 0 => {
+                if expect_name {
+                    names.push(word.to_string());
+                    expect_name = false;
+                } else {
+                    expect_name = matches!(word, "query" | "mutation" | "subscription");
+                }
+            }
+            Token::Punct('{') => {
+                depth += 1;
+                expect_name = false;
+            }
+            Token::Punct('}') => depth -= 1,
+            _ => expect_name = false,
+        }
+    }
+
+    names
+}
+
+// 엔드포인트에 introspection 쿼리를 보내 스키마를 가져온다
+pub async fn introspect(client: &Client, url: &str, headers: HeaderMap) -> Result<Schema, String> {
+    let response = client
+        .post(url)
+        .headers(headers)
+        .header(CONTENT_TYPE, "application/json")
+        .body(json!({ "query": INTROSPECTION_QUERY }).to_string())
+        .send()
+        .await
+        .map_err(|e| format!("Introspection request failed: {}", e))?;
+
+    let status = response.status();
+    let body: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid introspection response ({}): {}", status, e))?;
+
+    if let Some(errors) = body.get("errors").and_then(Value::as_array) {
+        let messages: Vec<&str> = errors
+            .iter()
+            .filter_map(|error| error.get("message").and_then(Value::as_str))
+            .collect();
+        if !messages.is_empty() {
+            return Err(format!("Introspection failed: {}", messages.join("; ")));
+        }
+    }
+
+    let schema = body
+        .pointer("/data/__schema")
+        .cloned()
+        .ok_or_else(|| format!("Introspection response has no schema ({})", status))?;
+    serde_json::from_value(schema).map_err(|e| format!("Failed to parse schema: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(kind: &str, name: &str) -> Value {
+        json!({ "kind": kind, "name": name, "ofType": null })
+    }
+
+    fn field(name: &str, ty: Value) -> Value {
+        json!({ "name": name, "description": null, "args": [], "type": ty })
+    }
+
+    // Query { viewer: User!, user(id: ID!): User }, User { id, name, friends: [User!] },
+    // Mutation { rename: User }
+    fn schema() -> Schema {
+        let user = || named("OBJECT", "User");
+        let introspection = json!({
+            "queryType": { "name": "Query" },
+            "mutationType": { "name": "Mutation" },
+            "subscriptionType": null,
+            "types": [
+                {
+                    "kind": "OBJECT", "name": "Query", "description": null,
+                    "fields": [
+                        field("viewer", json!({ "kind": "NON_NULL", "name": null, "ofType": user() })),
+                        {
+                            "name": "user", "description": null,
+                            "args": [{
+                                "name": "id",
+                                "type": { "kind": "NON_NULL", "name": null, "ofType": named("SCALAR", "ID") }
+                            }],
+                            "type": user()
+                        }
+                    ]
+                },
+                {
+                    "kind": "OBJECT", "name": "User", "description": null,
+                    "fields": [
+                        field("id", named("SCALAR", "ID")),
+                        field("name", named("SCALAR", "String")),
+                        field("friends", json!({
+                            "kind": "LIST", "name": null,
+                            "ofType": { "kind": "NON_NULL", "name": null, "ofType": user() }
+                        }))
+                    ]
+                },
+                {
+                    "kind": "OBJECT", "name": "Mutation", "description": null,
+                    "fields": [field("rename", user())]
+                },
+                { "kind": "SCALAR", "name": "ID", "description": null },
+                { "kind": "SCALAR", "name": "String", "description": null }
+            ]
+        });
+        serde_json::from_value(introspection).unwrap()
+    }
+
+    // 쿼리 끝에서 완성할 수 있는 필드 이름
+    fn complete(schema: &Schema, query: &str) -> Vec<String> {
+        let (_, fields) = schema.completions(query, query.len());
+        fields.iter().map(|field| field.name.clone()).collect()
+    }
+
+    #[test]
+    fn parses_introspection_types() {
+        let schema = schema();
+        assert_eq!(
+            schema.root_types(),
+            vec![("Query", "Query"), ("Mutation", "Mutation")]
+        );
+        let user = &schema.find_type("Query").unwrap().fields.as_ref().unwrap()[1];
+        assert_eq!(user.signature(), "user(id: ID!): User");
+        let friends = &schema.find_type("User").unwrap().fields.as_ref().unwrap()[2];
+        assert_eq!(friends.ty.display(), "[User!]");
+        assert_eq!(friends.ty.named(), "User");
+    }
+
+    #[test]
+    fn completes_fields_of_the_enclosing_selection() {
+        let schema = schema();
+        assert_eq!(complete(&schema, "{ "), vec!["viewer", "user"]);
+        assert_eq!(complete(&schema, "query { us"), vec!["user"]);
+        assert_eq!(complete(&schema, "{ viewer { friends { n"), vec!["name"]);
+        assert_eq!(
+            complete(&schema, "query Q($id: ID!) { user(id: $id) { "),
+            vec!["id", "name", "friends"]
+        );
+        // 이미 다 입력한 이름은 제안하지 않는다
+        assert!(complete(&schema, "{ viewer { name").is_empty());
+        // 알 수 없는 필드 아래에서는 제안이 없다
+        assert!(complete(&schema, "{ missing { ").is_empty());
+
+        let (start, _) = schema.completions("{ viewer { na", 13);
+        assert_eq!(start, 11);
+    }
+
+    #[test]
+    fn completes_through_aliases_fragments_and_comments() {
+        let schema = schema();
+        assert_eq!(complete(&schema, "{ me: viewer { i"), vec!["id"]);
+        assert_eq!(
+            complete(&schema, "fragment F on User { fr"),
+            vec!["friends"]
+        );
+        assert_eq!(
+            complete(&schema, "{ viewer { ... on User { na"),
+            vec!["name"]
+        );
+        assert_eq!(
+            complete(&schema, "{ viewer { # user {\n name \"{\" fr"),
+            vec!["friends"]
+        );
+        assert_eq!(
+            complete(&schema, "{ viewer @include(if: true) { i"),
+            vec!["id"]
+        );
+    }
+
+    #[test]
+    fn uses_the_root_type_of_the_current_operation() {
+        let schema = schema();
+        let query = "query A { viewer { id } }\nmutation B { ";
+        assert_eq!(complete(&schema, query), vec!["rename"]);
+        assert_eq!(
+            complete(&schema, "mutation B { rename { id } }\n{ vi"),
+            vec!["viewer"]
+        );
+        assert!(complete(&schema, "subscription { ").is_empty());
+    }
+
+    #[test]
+    fn lists_named_operations() {
+        let query = r#"
+            query GetUser($id: ID!) { user(id: $id) { ...UserFields } }
+            mutation Rename { rename { id } }
+            fragment UserFields on User { name }
+            { viewer { id } }
+            subscription OnChange { viewer { id } }
+        "#;
+        assert_eq!(
+            operation_names(query),
+            vec!["GetUser", "Rename", "OnChange"]
+        );
+        assert!(operation_names("{ viewer { id } }").is_empty());
+    }
+
+    #[test]
+    fn body_includes_variables_and_operation_name() {
+        let mut request = GraphQlRequest {
+            query: "query A { viewer { id } }".to_string(),
+            ..Default::default()
+        };
+        let body: Value = serde_json::from_str(&request.body().unwrap()).unwrap();
+        assert_eq!(body, json!({ "query": "query A { viewer { id } }" }));
+
+        request.variables = r#"{"id": 1}"#.to_string();
+        request.operation_name = Some("A".to_string());
+        let body: Value = serde_json::from_str(&request.body().unwrap()).unwrap();
+        assert_eq!(body["variables"], json!({ "id": 1 }));
+        assert_eq!(body["operationName"], json!("A"));
+
+        request.variables = "{".to_string();
+        assert!(request.body().is_err());
+    }
+}
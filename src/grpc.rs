@@ -0,0 +1,554 @@
+use prost::bytes::Buf;
+use prost::Message as _;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, SerializeOptions};
+use prost_types::{FileDescriptorProto, FileDescriptorSet};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::mpsc::unbounded_channel;
+use tokio_util::sync::CancellationToken;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::MetadataMap;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+use tonic::{Code, Request, Status};
+use tonic_prost::ProstCodec;
+use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+use tonic_reflection::pb::v1::{ServerReflectionRequest, ServerReflectionResponse};
+
+// protoc 출력 파일 이름에 붙이는 일련번호
+static PROTOSET_COUNTER: AtomicU64 = AtomicU64::new(0);
+// v1을 지원하지 않는 서버는 v1alpha로 다시 시도한다. 메시지 형식은 같다
+const REFLECTION_PATHS: [&str; 2] = [
+    "/grpc.reflection.v1.ServerReflection/ServerReflectionInfo",
+    "/grpc.reflection.v1alpha.ServerReflection/ServerReflectionInfo",
+];
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GrpcRequest {
+    // .proto 파일 또는 protoc로 만든 디스크립터 셋(.protoset, .pb)
+    pub proto_files: Vec<String>,
+    pub use_reflection: bool,
+    pub service: String,
+    pub method: String,
+    // 요청 메시지 JSON. 클라이언트 스트리밍이면 메시지 배열
+    pub message: String,
+    #[serde(skip)]
+    pub pool: Option<DescriptorPool>,
+}
+
+impl GrpcRequest {
+    pub fn method_descriptor(&self) -> Option<prost_reflect::MethodDescriptor> {
+        self.pool
+            .as_ref()?
+            .get_service_by_name(&self.service)?
+            .methods()
+            .find(|method| method.name() == self.method)
+    }
+}
+
+pub enum GrpcEvent {
+    Headers(Vec<(String, String)>),
+    Message(String),
+    Finished {
+        code: Code,
+        message: String,
+        trailers: Vec<(String, String)>,
+    },
+}
+
+// 진행 중인 호출과 받은 응답. UI에서만 쓰고 저장하지 않는다
+#[derive(Default)]
+pub struct Session {
+    pub id: u64,
+    pub cancel: Option<CancellationToken>,
+    pub status: Option<String>,
+    pub headers: Vec<(String, String)>,
+    pub messages: Vec<String>,
+    pub trailers: Vec<(String, String)>,
+}
+
+impl Session {
+    // 새 호출을 위해 응답을 비우고 취소 토큰을 돌려준다
+    pub fn start(&mut self) -> (u64, CancellationToken) {
+        self.cancel();
+        let cancel = CancellationToken::new();
+        self.id += 1;
+        self.cancel = Some(cancel.clone());
+        self.status = Some("Calling...".to_string());
+        self.headers.clear();
+        self.messages.clear();
+        self.trailers.clear();
+        (self.id, cancel)
+    }
+
+    pub fn cancel(&mut self) {
+        if let Some(cancel) = self.cancel.take() {
+            cancel.cancel();
+            self.status = Some("Cancelled".to_string());
+        }
+    }
+
+    pub fn handle(&mut self, event: GrpcEvent) {
+        match event {
+            GrpcEvent::Headers(headers) => self.headers = headers,
+            GrpcEvent::Message(message) => self.messages.push(message),
+            GrpcEvent::Finished {
+                code,
+                message,
+                trailers,
+            } => {
+                self.cancel = None;
+                self.trailers = trailers;
+                self.status = Some(if message.is_empty() {
+                    format!("{:?}", code)
+                } else {
+                    format!("{:?}: {}", code, message)
+                });
+            }
+        }
+    }
+}
+
+// 메소드 종류 표시
+pub fn method_kind(method: &prost_reflect::MethodDescriptor) -> &'static str {
+    match (method.is_client_streaming(), method.is_server_streaming()) {
+        (false, false) => "unary",
+        (true, false) => "client streaming",
+        (false, true) => "server streaming",
+        (true, true) => "bidirectional streaming",
+    }
+}
+
+// 기본값을 모두 채운 요청 메시지 JSON
+pub fn message_template(method: &prost_reflect::MethodDescriptor) -> String {
+    let message = DynamicMessage::new(method.input());
+    let options = SerializeOptions::new().skip_default_fields(false);
+    let value = message
+        .serialize_with_options(serde_json::value::Serializer, &options)
+        .unwrap_or_else(|_| serde_json::json!({}));
+    let value = if method.is_client_streaming() {
+        serde_json::Value::Array(vec![value])
+    } else {
+        value
+    };
+    serde_json::to_string_pretty(&value).unwrap_or_default()
+}
+
+// 디스크립터 셋은 그대로 읽고, .proto 파일은 protoc로 디스크립터 셋을 만든다
+pub fn load_files(files: &[String]) -> Result<DescriptorPool, String> {
+    let mut pool = DescriptorPool::new();
+    let (protos, sets): (Vec<&String>, Vec<&String>) =
+        files.iter().partition(|file| file.ends_with(".proto"));
+
+    for file in sets {
+        let bytes = std::fs::read(file).map_err(|e| format!("Failed to read '{}': {}", file, e))?;
+        pool.decode_file_descriptor_set(bytes.as_slice())
+            .map_err(|e| format!("Invalid descriptor set '{}': {}", file, e))?;
+    }
+
+    if !protos.is_empty() {
+        // 동시에 여러 번 불러와도 겹치지 않도록 호출마다 다른 파일을 쓴다
+        let output = std::env::temp_dir().join(format!(
+            "ruquest-{}-{}.protoset",
+            std::process::id(),
+            PROTOSET_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let protoc = std::env::var("PROTOC").unwrap_or_else(|_| "protoc".to_string());
+        let mut command = Command::new(&protoc);
+        command
+            .arg("--include_imports")
+            .arg(format!("--descriptor_set_out={}", output.display()));
+        let mut include_dirs: Vec<&Path> = Vec::new();
+        for file in &protos {
+            let dir = Path::new(file.as_str())
+                .parent()
+                .unwrap_or_else(|| Path::new("."));
+            if !include_dirs.contains(&dir) {
+                include_dirs.push(dir);
+                command.arg("-I").arg(dir);
+            }
+        }
+        command.args(&protos);
+
+        let result = command.output();
+        // 실패했을 때도 protoc가 남긴 파일을 지운다
+        let bytes = std::fs::read(&output);
+        let _ = std::fs::remove_file(&output);
+        let result = result
+            .map_err(|e| format!("Failed to run '{}' (set PROTOC to its path): {}", protoc, e))?;
+        if !result.status.success() {
+            return Err(format!(
+                "protoc failed: {}",
+                String::from_utf8_lossy(&result.stderr).trim()
+            ));
+        }
+        let bytes = bytes.map_err(|e| e.to_string())?;
+        pool.decode_file_descriptor_set(bytes.as_slice())
+            .map_err(|e| format!("Invalid descriptor set from protoc: {}", e))?;
+    }
+
+    Ok(pool)
+}
+
+// 스킴이 없으면 평문 HTTP/2로 연결한다
+pub async fn connect(url: &str) -> Result<Channel, String> {
+    let url = if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("http://{}", url)
+    };
+    let mut endpoint = Endpoint::from_shared(url.clone())
+        .map_err(|e| format!("Invalid gRPC URL '{}': {}", url, e))?;
+    if url.starts_with("https://") {
+        endpoint = endpoint
+            .tls_config(ClientTlsConfig::new().with_native_roots())
+            .map_err(|e| e.to_string())?;
+    }
+    endpoint
+        .connect()
+        .await
+        .map_err(|e| format!("Failed to connect to '{}': {}", url, e))
+}
+
+// 서버 리플렉션으로 서비스 목록과 필요한 파일 디스크립터를 모두 받아온다
+pub async fn load_reflection(
+    channel: Channel,
+    metadata: &HeaderMap,
+) -> Result<DescriptorPool, String> {
+    let mut last_error = String::new();
+    for path in REFLECTION_PATHS {
+        match reflect(channel.clone(), metadata, path).await {
+            Ok(pool) => return Ok(pool),
+            Err(status) if status.code() == Code::Unimplemented => {
+                last_error = status.message().to_string();
+            }
+            Err(status) => return Err(format!("Reflection failed: {}", status.message())),
+        }
+    }
+    Err(format!(
+        "Server reflection is not available: {}",
+        last_error
+    ))
+}
+
+async fn reflect(
+    channel: Channel,
+    metadata: &HeaderMap,
+    path: &'static str,
+) -> Result<DescriptorPool, Status> {
+    let (requests, receiver) = unbounded_channel::<ServerReflectionRequest>();
+    let stream = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|request| (request, receiver))
+    });
+    let send = |message_request: MessageRequest| {
+        let _ = requests.send(ServerReflectionRequest {
+            host: String::new(),
+            message_request: Some(message_request),
+        });
+    };
+
+    let mut grpc = tonic::client::Grpc::new(channel);
+    grpc.ready()
+        .await
+        .map_err(|e| Status::unavailable(e.to_string()))?;
+    let mut request = Request::new(stream);
+    *request.metadata_mut() = MetadataMap::from_headers(metadata.clone());
+    send(MessageRequest::ListServices(String::new()));
+    let codec: ProstCodec<ServerReflectionRequest, ServerReflectionResponse> = ProstCodec::new();
+    let mut responses = grpc
+        .streaming(request, PathAndQuery::from_static(path), codec)
+        .await?
+        .into_inner();
+
+    let mut files: Vec<FileDescriptorProto> = Vec::new();
+    let mut requested: HashSet<String> = HashSet::new();
+    let mut pending = 1;
+    while pending > 0 {
+        let Some(response) = responses.message().await? else {
+            break;
+        };
+        pending -= 1;
+        match response.message_response {
+            Some(MessageResponse::ListServicesResponse(list)) => {
+                for service in list.service {
+                    send(MessageRequest::FileContainingSymbol(service.name));
+                    pending += 1;
+                }
+            }
+            Some(MessageResponse::FileDescriptorResponse(response)) => {
+                let mut received = Vec::new();
+                for bytes in response.file_descriptor_proto {
+                    let file = FileDescriptorProto::decode(bytes.as_slice())
+                        .map_err(|e| Status::internal(e.to_string()))?;
+                    requested.insert(file.name().to_string());
+                    if !files.iter().chain(&received).any(|f| f.name == file.name) {
+                        received.push(file);
+                    }
+                }
+                // 서버가 함께 보내주지 않은 의존 파일은 이름으로 다시 요청한다
+                for file in &received {
+                    for dependency in &file.dependency {
+                        if requested.insert(dependency.clone()) {
+                            send(MessageRequest::FileByFilename(dependency.clone()));
+                            pending += 1;
+                        }
+                    }
+                }
+                files.extend(received);
+            }
+            Some(MessageResponse::ErrorResponse(error)) => {
+                return Err(Status::new(
+                    Code::from_i32(error.error_code),
+                    error.error_message,
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    DescriptorPool::from_file_descriptor_set(FileDescriptorSet { file: files })
+        .map_err(|e| Status::internal(format!("Invalid descriptors from reflection: {}", e)))
+}
+
+// 스키마를 모르는 메시지를 디스크립터로 인코딩/디코딩하는 코덱
+struct DynamicCodec {
+    output: MessageDescriptor,
+}
+
+struct DynamicEncoder;
+
+struct DynamicDecoder(MessageDescriptor);
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicEncoder;
+    type Decoder = DynamicDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        DynamicEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        DynamicDecoder(self.output.clone())
+    }
+}
+
+impl Encoder for DynamicEncoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        item.encode(dst)
+            .map_err(|e| Status::internal(format!("Failed to encode message: {}", e)))
+    }
+}
+
+impl Decoder for DynamicDecoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        let bytes = src.copy_to_bytes(src.remaining());
+        DynamicMessage::decode(self.0.clone(), bytes)
+            .map(Some)
+            .map_err(|e| Status::internal(format!("Failed to decode message: {}", e)))
+    }
+}
+
+fn metadata_pairs(metadata: &MetadataMap) -> Vec<(String, String)> {
+    metadata
+        .clone()
+        .into_headers()
+        .iter()
+        .map(|(key, value)| {
+            (
+                key.to_string(),
+                value.to_str().unwrap_or("<binary>").to_string(),
+            )
+        })
+        .collect()
+}
+
+// 요청 JSON을 메시지 목록으로 바꾼다. 클라이언트 스트리밍은 JSON 배열을 받는다
+fn parse_messages(
+    method: &prost_reflect::MethodDescriptor,
+    json: &str,
+) -> Result<Vec<DynamicMessage>, String> {
+    let json = if json.trim().is_empty() { "{}" } else { json };
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| format!("Invalid request JSON: {}", e))?;
+    let values = match value {
+        serde_json::Value::Array(values) if method.is_client_streaming() => values,
+        value => vec![value],
+    };
+    values
+        .into_iter()
+        .map(|value| {
+            DynamicMessage::deserialize(method.input(), value)
+                .map_err(|e| format!("Request does not match {}: {}", method.input().name(), e))
+        })
+        .collect()
+}
+
+// 메소드를 호출하고 응답 헤더, 메시지, 최종 상태를 순서대로 전달한다.
+// 네 가지 호출 방식 모두 스트리밍 호출로 보내며 단항 호출은 메시지가 하나인 스트림이 된다
+pub async fn call(
+    channel: Channel,
+    method: prost_reflect::MethodDescriptor,
+    metadata: HeaderMap,
+    json: String,
+    emit: impl Fn(GrpcEvent),
+) {
+    let finish = |code: Code, message: String, trailers: Vec<(String, String)>| {
+        emit(GrpcEvent::Finished {
+            code,
+            message,
+            trailers,
+        })
+    };
+    let messages = match parse_messages(&method, &json) {
+        Ok(messages) => messages,
+        Err(e) => return finish(Code::InvalidArgument, e, Vec::new()),
+    };
+    let path = format!("/{}/{}", method.parent_service().full_name(), method.name());
+    let path = match PathAndQuery::try_from(path) {
+        Ok(path) => path,
+        Err(e) => return finish(Code::InvalidArgument, e.to_string(), Vec::new()),
+    };
+
+    let mut grpc = tonic::client::Grpc::new(channel);
+    if let Err(e) = grpc.ready().await {
+        return finish(Code::Unavailable, e.to_string(), Vec::new());
+    }
+    let mut request = Request::new(futures_util::stream::iter(messages));
+    *request.metadata_mut() = MetadataMap::from_headers(metadata);
+    let codec = DynamicCodec {
+        output: method.output(),
+    };
+
+    let response = match grpc.streaming(request, path, codec).await {
+        Ok(response) => response,
+        Err(status) => {
+            let trailers = metadata_pairs(status.metadata());
+            return finish(status.code(), status.message().to_string(), trailers);
+        }
+    };
+    emit(GrpcEvent::Headers(metadata_pairs(response.metadata())));
+
+    let mut stream = response.into_inner();
+    loop {
+        match stream.message().await {
+            Ok(Some(message)) => {
+                let json = serde_json::to_string_pretty(&message)
+                    .unwrap_or_else(|e| format!("Failed to format message: {}", e));
+                emit(GrpcEvent::Message(json));
+            }
+            Ok(None) => break,
+            Err(status) => {
+                let trailers = metadata_pairs(status.metadata());
+                return finish(status.code(), status.message().to_string(), trailers);
+            }
+        }
+    }
+    let trailers = match stream.trailers().await {
+        Ok(Some(trailers)) => metadata_pairs(&trailers),
+        _ => Vec::new(),
+    };
+    finish(Code::Ok, String::new(), trailers);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tokio_stream::wrappers::TcpListenerStream;
+
+    // 리플렉션 서비스만 있는 로컬 서버. 리플렉션 자체를 호출 대상으로도 쓴다
+    async fn reflection_server() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let reflection = tonic_reflection::server::Builder::configure()
+            .register_encoded_file_descriptor_set(tonic_reflection::pb::v1::FILE_DESCRIPTOR_SET)
+            .build_v1()
+            .unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(reflection)
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        format!("127.0.0.1:{}", addr.port())
+    }
+
+    #[tokio::test]
+    async fn loads_services_and_calls_through_reflection() {
+        let url = reflection_server().await;
+        let channel = connect(&url).await.unwrap();
+        let pool = load_reflection(channel.clone(), &HeaderMap::new())
+            .await
+            .unwrap();
+        let service = pool
+            .get_service_by_name("grpc.reflection.v1.ServerReflection")
+            .unwrap();
+        let method = service
+            .methods()
+            .find(|method| method.name() == "ServerReflectionInfo")
+            .unwrap();
+        assert_eq!(method_kind(&method), "bidirectional streaming");
+
+        let events = Mutex::new(Vec::new());
+        call(
+            channel,
+            method,
+            HeaderMap::new(),
+            r#"[{ "listServices": "" }]"#.to_string(),
+            |event| events.lock().unwrap().push(event),
+        )
+        .await;
+        let events = events.into_inner().unwrap();
+        assert!(matches!(events.first(), Some(GrpcEvent::Headers(_))));
+        assert!(events.iter().any(|event| matches!(event,
+            GrpcEvent::Message(json) if json.contains("grpc.reflection.v1.ServerReflection"))));
+        assert!(matches!(
+            events.last(),
+            Some(GrpcEvent::Finished { code: Code::Ok, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn call_rejects_messages_that_do_not_match_the_input() {
+        let url = reflection_server().await;
+        let channel = connect(&url).await.unwrap();
+        let pool = load_reflection(channel.clone(), &HeaderMap::new())
+            .await
+            .unwrap();
+        let method = pool
+            .get_service_by_name("grpc.reflection.v1.ServerReflection")
+            .and_then(|service| service.methods().next())
+            .unwrap();
+
+        let events = Mutex::new(Vec::new());
+        call(
+            channel,
+            method,
+            HeaderMap::new(),
+            r#"[{ "noSuchField": 1 }]"#.to_string(),
+            |event| events.lock().unwrap().push(event),
+        )
+        .await;
+        let events = events.into_inner().unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [GrpcEvent::Finished {
+                code: Code::InvalidArgument,
+                ..
+            }]
+        ));
+    }
+}
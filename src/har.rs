@@ -0,0 +1,594 @@
+use crate::auth::{ApiKeyLocation, Auth};
+use crate::body::{decode_form, BodyType, FormPart, FormPartKind};
+use crate::history::{HistoryEntry, ResponseExample};
+use crate::import::{str_field, unique_request_name};
+use crate::params::{sync_params_from_url, sync_path_params, KeyValue};
+use crate::{ApiRequest, RequestGroup};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::{json, Value};
+
+// 클라이언트가 직접 채우는 헤더. HTTP/2의 :authority 같은 의사 헤더도 함께 건너뛴다.
+// Accept-Encoding은 압축된 응답을 풀지 못하므로 가져오지 않는다
+const SKIPPED_HEADERS: [&str; 4] = ["host", "content-length", "connection", "accept-encoding"];
+
+// 공유하는 HAR 파일에 남기지 않는 인증 헤더
+const SENSITIVE_HEADERS: [&str; 7] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "api-key",
+    "x-auth-token",
+];
+const REDACTED: &str = "<redacted>";
+
+pub fn is_har(value: &Value) -> bool {
+    value.pointer("/log/entries").is_some_and(Value::is_array)
+}
+
+// HAR 1.2의 각 entry를 요청 하나로 만든다. with_responses면 기록된 응답을 예시로 붙인다
+pub fn import(
+    value: &Value,
+    name: &str,
+    with_responses: bool,
+) -> Result<(RequestGroup, Vec<String>), String> {
+    let entries = value
+        .pointer("/log/entries")
+        .and_then(Value::as_array)
+        .ok_or("Not a HAR file: missing log.entries")?;
+    let mut group = RequestGroup {
+        name: name.to_string(),
+        is_expanded: true,
+        ..Default::default()
+    };
+    let mut warnings = Vec::new();
+
+    for (idx, entry) in entries.iter().enumerate() {
+        let Some(request) = entry.get("request") else {
+            warnings.push(format!("Entry {} has no request and was skipped", idx + 1));
+            continue;
+        };
+        let mut req = ApiRequest {
+            method: str_field(request, "method").to_uppercase(),
+            url: str_field(request, "url"),
+            headers: headers(request)
+                .into_iter()
+                .filter(|(key, _)| {
+                    !key.starts_with(':') && !SKIPPED_HEADERS.contains(&key.to_lowercase().as_str())
+                })
+                .map(|(key, value)| KeyValue::new(key, value))
+                .collect(),
+            body_type: BodyType::None,
+            ..Default::default()
+        };
+        let path = reqwest::Url::parse(&req.url)
+            .map(|url| url.path().to_string())
+            .unwrap_or_else(|_| req.url.clone());
+        req.name = unique_request_name(&group.requests, &format!("{} {}", req.method, path));
+
+        if let Some(post_data) = request.get("postData") {
+            post_body(&mut req, post_data, &mut warnings);
+        }
+        sync_params_from_url(&req.url, &mut req.params);
+        sync_path_params(&req.url, &mut req.path_params);
+
+        if with_responses {
+            if let Some(response) = entry.get("response") {
+                req.examples.push(example(entry, response));
+            }
+        }
+        group.requests.push(req);
+    }
+    Ok((group, warnings))
+}
+
+fn post_body(req: &mut ApiRequest, post_data: &Value, warnings: &mut Vec<String>) {
+    let mime_type = str_field(post_data, "mimeType").to_lowercase();
+    let text = str_field(post_data, "text");
+    let params = post_data
+        .get("params")
+        .and_then(Value::as_array)
+        .filter(|params| !params.is_empty());
+
+    if mime_type.starts_with("multipart/form-data") {
+        req.body_type = BodyType::FormData;
+        match params {
+            Some(params) => {
+                req.form_data = params
+                    .iter()
+                    .map(|param| {
+                        let mut part = FormPart {
+                            key: str_field(param, "name"),
+                            content_type: str_field(param, "contentType"),
+                            ..Default::default()
+                        };
+                        // 파일 내용은 HAR에 없으므로 파일 이름만 남기고 경로는 다시 지정해야 한다
+                        match param.get("fileName").and_then(Value::as_str) {
+                            Some(file_name) => {
+                                part.kind = FormPartKind::File;
+                                part.file_name = file_name.to_string();
+                                warnings.push(format!(
+                                    "'{}': choose the file for form field '{}'",
+                                    req.name, part.key
+                                ));
+                            }
+                            None => part.value = str_field(param, "value"),
+                        }
+                        part
+                    })
+                    .collect();
+            }
+            // 파트 목록이 없으면 boundary가 포함된 원문을 그대로 보낸다
+            None => {
+                req.body_type = BodyType::Text;
+                req.body = text;
+                return;
+            }
+        }
+    } else if mime_type.starts_with("application/x-www-form-urlencoded") {
+        req.body_type = BodyType::FormUrlEncoded;
+        req.url_encoded = match params {
+            Some(params) => params
+                .iter()
+                .map(|param| KeyValue::new(str_field(param, "name"), str_field(param, "value")))
+                .collect(),
+            None => decode_form(&text)
+                .into_iter()
+                .map(|(key, value)| KeyValue::new(key, value))
+                .collect(),
+        };
+        req.body = text;
+    } else {
+        req.body_type = if mime_type.contains("json") {
+            BodyType::Json
+        } else if mime_type.contains("xml") {
+            BodyType::Xml
+        } else {
+            BodyType::Text
+        };
+        req.body = text;
+    }
+
+    // 바디 종류로 정해지는 Content-Type과 같거나 boundary가 든 헤더는 빼 둔다
+    let implied = req.body_type.content_type();
+    req.headers.retain(|header| {
+        !header.key.eq_ignore_ascii_case("Content-Type")
+            || (req.body_type != BodyType::FormData
+                && implied.is_none_or(|implied| !header.value.eq_ignore_ascii_case(implied)))
+    });
+}
+
+fn example(entry: &Value, response: &Value) -> ResponseExample {
+    let status = response
+        .get("status")
+        .and_then(Value::as_u64)
+        .unwrap_or_default() as u16;
+    let content = response.get("content").unwrap_or(&Value::Null);
+    let text = str_field(content, "text");
+    // 바이너리 응답은 base64로 기록되므로 텍스트로 풀리는 경우에만 보여준다
+    let body = if content.get("encoding").and_then(Value::as_str) == Some("base64") {
+        match STANDARD.decode(text.trim()) {
+            Ok(bytes) => String::from_utf8(bytes)
+                .unwrap_or_else(|e| format!("<binary content: {} bytes>", e.as_bytes().len())),
+            Err(_) => text,
+        }
+    } else {
+        text
+    };
+    let time_ms = entry
+        .get("time")
+        .and_then(Value::as_f64)
+        .unwrap_or_default()
+        .max(0.0) as u64;
+    ResponseExample::new(
+        format!("{} {}", status, str_field(response, "statusText"))
+            .trim()
+            .to_string(),
+        status,
+        headers(response)
+            .into_iter()
+            .filter(|(key, _)| !key.starts_with(':'))
+            .collect(),
+        body,
+        time_ms,
+    )
+}
+
+// 기록 항목들을 브라우저 개발자 도구에서 열 수 있는 HAR 1.2 문서로 만든다
+pub fn export(entries: &[&HistoryEntry]) -> Value {
+    let entries: Vec<Value> = entries
+        .iter()
+        .map(|entry| {
+            // API 키 인증에 쓴 헤더나 쿼리 이름도 함께 가린다
+            let (api_key_header, api_key_query) = match &entry.request.auth {
+                Auth::ApiKey { key, location, .. } => match location {
+                    ApiKeyLocation::Header => (Some(key.as_str()), None),
+                    ApiKeyLocation::Query => (None, Some(key.as_str())),
+                },
+                _ => (None, None),
+            };
+            let sensitive = |name: &str| {
+                SENSITIVE_HEADERS.contains(&name.to_ascii_lowercase().as_str())
+                    || api_key_header.is_some_and(|key| key.eq_ignore_ascii_case(name))
+            };
+            let export_headers = |headers: &[(String, String)]| -> Vec<Value> {
+                headers
+                    .iter()
+                    .map(|(name, value)| {
+                        let value = if sensitive(name) { REDACTED } else { value };
+                        json!({ "name": name, "value": value })
+                    })
+                    .collect()
+            };
+
+            let mut url = entry.url.clone();
+            let mut query: Vec<Value> = Vec::new();
+            if let Ok(mut parsed) = reqwest::Url::parse(&entry.url) {
+                let pairs: Vec<(String, String)> = parsed
+                    .query_pairs()
+                    .map(|(name, value)| {
+                        let value = if api_key_query == Some(name.as_ref()) {
+                            REDACTED.to_string()
+                        } else {
+                            value.into_owned()
+                        };
+                        (name.into_owned(), value)
+                    })
+                    .collect();
+                if api_key_query.is_some_and(|key| pairs.iter().any(|(name, _)| name == key)) {
+                    parsed.query_pairs_mut().clear().extend_pairs(&pairs);
+                    url = parsed.to_string();
+                }
+                query = pairs
+                    .iter()
+                    .map(|(name, value)| json!({ "name": name, "value": value }))
+                    .collect();
+            }
+            let mut request = json!({
+                "method": entry.method,
+                "url": url,
+                "httpVersion": "HTTP/1.1",
+                "cookies": [],
+                "headers": export_headers(&entry.headers),
+                "queryString": query,
+                "headersSize": -1,
+                "bodySize": entry.body_size.map_or(-1, |size| size as i64),
+            });
+            if let Some(post_data) = export_post_data(entry) {
+                request["postData"] = post_data;
+            }
+
+            json!({
+                "startedDateTime": entry.timestamp,
+                "time": entry.time_ms,
+                "request": request,
+                "response": {
+                    "status": entry.status,
+                    "statusText": reqwest::StatusCode::from_u16(entry.status)
+                        .ok()
+                        .and_then(|status| status.canonical_reason())
+                        .unwrap_or_default(),
+                    "httpVersion": "HTTP/1.1",
+                    "cookies": [],
+                    "headers": export_headers(&entry.response_headers),
+                    "content": {
+                        "size": entry.response_body.len(),
+                        "mimeType": header_value(&entry.response_headers, "Content-Type")
+                            .unwrap_or_default(),
+                        "text": entry.response_body,
+                    },
+                    "redirectURL": header_value(&entry.response_headers, "Location")
+                        .unwrap_or_default(),
+                    "headersSize": -1,
+                    "bodySize": entry.response_body.len(),
+                },
+                "cache": {},
+                "timings": { "send": 0, "wait": entry.time_ms, "receive": 0 },
+            })
+        })
+        .collect();
+
+    json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "Ruquest", "version": env!("CARGO_PKG_VERSION") },
+            "entries": entries,
+        }
+    })
+}
+
+// multipart는 파트 목록으로, 파일과 잘린 바디는 내용 없이 설명만 남긴다
+fn export_post_data(entry: &HistoryEntry) -> Option<Value> {
+    let mime_type = header_value(&entry.headers, "Content-Type")
+        .or_else(|| entry.request.body_type.content_type())
+        .unwrap_or_default();
+    match entry.request.body_type {
+        BodyType::FormData => {
+            let params: Vec<Value> = entry
+                .form_data
+                .iter()
+                .filter(|part| part.enabled && !part.key.is_empty())
+                .map(|part| {
+                    let mut param = json!({ "name": part.key });
+                    match part.kind {
+                        FormPartKind::Text => param["value"] = json!(part.value),
+                        FormPartKind::File => {
+                            param["fileName"] = json!(if part.file_name.is_empty() {
+                                &part.file_path
+                            } else {
+                                &part.file_name
+                            })
+                        }
+                    }
+                    if !part.content_type.is_empty() {
+                        param["contentType"] = json!(part.content_type);
+                    }
+                    param
+                })
+                .collect();
+            // 파트 목록을 쓰므로 boundary 없는 multipart/form-data로 충분하다
+            Some(json!({ "mimeType": "multipart/form-data", "params": params }))
+        }
+        // 파일 내용은 기록하지 않으므로 "<file: 경로>"만 설명으로 남긴다
+        BodyType::Binary => Some(json!({ "mimeType": mime_type, "comment": entry.body })),
+        _ if entry.body.is_empty() => None,
+        _ if !entry.has_full_body() => Some(json!({
+            "mimeType": mime_type,
+            "comment": "Body was truncated in history",
+        })),
+        _ => Some(json!({ "mimeType": mime_type, "text": entry.body })),
+    }
+}
+
+fn header_value<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+fn headers(value: &Value) -> Vec<(String, String)> {
+    value
+        .get("headers")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|header| (str_field(header, "name"), str_field(header, "value")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ApiResponse;
+
+    fn entry(sent: ApiRequest) -> HistoryEntry {
+        HistoryEntry::new(None, &sent, &sent, &ApiResponse::error("offline"))
+    }
+
+    #[test]
+    fn import_reads_entries_and_bodies() {
+        let har = json!({ "log": { "entries": [
+            {
+                "request": {
+                    "method": "post",
+                    "url": "https://api.example.com/users?page=2",
+                    "headers": [
+                        { "name": ":authority", "value": "api.example.com" },
+                        { "name": "Content-Type", "value": "application/json" },
+                        { "name": "X-Trace", "value": "1" },
+                    ],
+                    "postData": { "mimeType": "application/json", "text": "{\"a\":1}" },
+                },
+                "response": {
+                    "status": 201,
+                    "statusText": "Created",
+                    "headers": [],
+                    "content": { "mimeType": "text/plain", "encoding": "base64", "text": "aGk=" },
+                },
+                "time": 12.5,
+            },
+            {
+                "request": {
+                    "method": "POST",
+                    "url": "https://api.example.com/users",
+                    "postData": {
+                        "mimeType": "multipart/form-data; boundary=x",
+                        "params": [
+                            { "name": "title", "value": "hi" },
+                            { "name": "avatar", "fileName": "a.png", "contentType": "image/png" },
+                        ],
+                    },
+                },
+            },
+        ] } });
+        let (group, warnings) = import(&har, "Capture", true).unwrap();
+
+        let first = &group.requests[0];
+        assert_eq!(first.name, "POST /users");
+        assert_eq!(first.method, "POST");
+        assert_eq!(first.body_type, BodyType::Json);
+        assert_eq!(first.body, "{\"a\":1}");
+        let headers: Vec<&str> = first.headers.iter().map(|h| h.key.as_str()).collect();
+        assert_eq!(headers, ["X-Trace"]);
+        assert_eq!(first.params[0].key, "page");
+        assert_eq!(first.examples[0].name, "201 Created");
+        assert_eq!(first.examples[0].body, "hi");
+
+        let second = &group.requests[1];
+        assert_eq!(second.name, "POST /users (2)");
+        assert_eq!(second.body_type, BodyType::FormData);
+        assert_eq!(second.form_data[0].value, "hi");
+        assert_eq!(second.form_data[1].kind, FormPartKind::File);
+        assert_eq!(second.form_data[1].file_name, "a.png");
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn export_writes_text_bodies_with_real_size() {
+        let sent = ApiRequest {
+            method: "POST".to_string(),
+            url: "https://api.example.com/users?q=a%20b".to_string(),
+            body_type: BodyType::Json,
+            body: "{\"a\":1}".to_string(),
+            ..Default::default()
+        };
+        let har = export(&[&entry(sent)]);
+        let request = &har["log"]["entries"][0]["request"];
+        assert_eq!(request["bodySize"], 7);
+        assert_eq!(request["postData"]["mimeType"], "application/json");
+        assert_eq!(request["postData"]["text"], "{\"a\":1}");
+        assert_eq!(request["queryString"][0]["value"], "a b");
+    }
+
+    #[test]
+    fn export_writes_multipart_as_params() {
+        let sent = ApiRequest {
+            method: "POST".to_string(),
+            url: "https://api.example.com/upload".to_string(),
+            body_type: BodyType::FormData,
+            form_data: vec![
+                FormPart {
+                    key: "title".to_string(),
+                    value: "hi".to_string(),
+                    ..Default::default()
+                },
+                FormPart {
+                    key: "avatar".to_string(),
+                    kind: FormPartKind::File,
+                    file_path: "/tmp/a.png".to_string(),
+                    file_name: "a.png".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let har = export(&[&entry(sent)]);
+        let request = &har["log"]["entries"][0]["request"];
+        assert_eq!(request["bodySize"], -1);
+        let post_data = &request["postData"];
+        assert_eq!(post_data["mimeType"], "multipart/form-data");
+        assert!(post_data.get("text").is_none());
+        assert_eq!(
+            post_data["params"][0],
+            json!({ "name": "title", "value": "hi" })
+        );
+        assert_eq!(
+            post_data["params"][1],
+            json!({ "name": "avatar", "fileName": "a.png" })
+        );
+
+        // 내보낸 파트는 다시 가져올 수 있어야 한다
+        let (group, _) = import(&har, "Again", false).unwrap();
+        assert_eq!(group.requests[0].form_data[0].value, "hi");
+        assert_eq!(group.requests[0].form_data[1].file_name, "a.png");
+    }
+
+    #[test]
+    fn export_omits_file_and_truncated_bodies() {
+        let file = ApiRequest {
+            method: "PUT".to_string(),
+            url: "https://api.example.com/blob".to_string(),
+            body_type: BodyType::Binary,
+            binary_path: "/nonexistent/blob.bin".to_string(),
+            ..Default::default()
+        };
+        let large = ApiRequest {
+            method: "POST".to_string(),
+            url: "https://api.example.com/bulk".to_string(),
+            body_type: BodyType::Text,
+            body: "x".repeat(300 * 1024),
+            ..Default::default()
+        };
+        let har = export(&[&entry(file), &entry(large)]);
+        let file = &har["log"]["entries"][0]["request"];
+        assert_eq!(file["bodySize"], -1);
+        assert!(file["postData"].get("text").is_none());
+        assert_eq!(file["postData"]["comment"], "<file: /nonexistent/blob.bin>");
+        let large = &har["log"]["entries"][1]["request"];
+        assert_eq!(large["bodySize"], 300 * 1024);
+        assert!(large["postData"].get("text").is_none());
+    }
+
+    #[test]
+    fn export_redacts_credentials() {
+        let sent = ApiRequest {
+            method: "GET".to_string(),
+            url: "https://api.example.com/items?page=2&key=abc%20123".to_string(),
+            headers: vec![
+                KeyValue::new("Authorization".to_string(), "Bearer t0k3n".to_string()),
+                KeyValue::new("cookie".to_string(), "session=s1".to_string()),
+                KeyValue::new("X-Custom-Key".to_string(), "c1".to_string()),
+                KeyValue::new("Accept".to_string(), "application/json".to_string()),
+            ],
+            ..Default::default()
+        };
+        let response = crate::ApiResponse {
+            status: 200,
+            ..crate::ApiResponse::error("")
+        };
+        let mut header_key = HistoryEntry::new(None, &sent, &sent, &response);
+        header_key.request.auth = Auth::ApiKey {
+            key: "x-custom-key".to_string(),
+            value: "c1".to_string(),
+            location: ApiKeyLocation::Header,
+        };
+        header_key.response_headers = vec![("Set-Cookie".to_string(), "session=s2".to_string())];
+        let mut query_key = header_key.clone();
+        query_key.request.auth = Auth::ApiKey {
+            key: "key".to_string(),
+            value: "abc 123".to_string(),
+            location: ApiKeyLocation::Query,
+        };
+
+        let har = export(&[&header_key, &query_key]);
+        let text = har.to_string();
+        for secret in ["t0k3n", "session=s1", "session=s2"] {
+            assert!(!text.contains(secret), "{} leaked", secret);
+        }
+        assert!(!har["log"]["entries"][1].to_string().contains("abc"));
+
+        let request = &har["log"]["entries"][0]["request"];
+        let headers: Vec<(&str, &str)> = request["headers"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|h| (h["name"].as_str().unwrap(), h["value"].as_str().unwrap()))
+            .collect();
+        assert_eq!(
+            headers,
+            vec![
+                ("Authorization", REDACTED),
+                ("cookie", REDACTED),
+                ("X-Custom-Key", REDACTED),
+                ("Accept", "application/json"),
+            ]
+        );
+        assert_eq!(
+            har["log"]["entries"][0]["response"]["headers"][0]["value"],
+            REDACTED
+        );
+
+        // 헤더 API 키 요청은 URL을 그대로 두고, 쿼리 API 키 요청은 값을 가린다
+        assert_eq!(
+            request["url"],
+            "https://api.example.com/items?page=2&key=abc%20123"
+        );
+        let request = &har["log"]["entries"][1]["request"];
+        assert_eq!(
+            request["url"],
+            "https://api.example.com/items?page=2&key=%3Credacted%3E"
+        );
+        assert_eq!(
+            request["queryString"][0],
+            json!({ "name": "page", "value": "2" })
+        );
+        assert_eq!(
+            request["queryString"][1],
+            json!({ "name": "key", "value": REDACTED })
+        );
+        assert_eq!(request["headers"][2]["value"], "c1");
+    }
+}
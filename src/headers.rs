@@ -0,0 +1,76 @@
+// 헤더 이름/값 자동완성 후보
+pub const HEADER_NAMES: &[&str] = &[
+    "Accept",
+    "Accept-Charset",
+    "Accept-Encoding",
+    "Accept-Language",
+    "Authorization",
+    "Cache-Control",
+    "Connection",
+    "Content-Disposition",
+    "Content-Encoding",
+    "Content-Language",
+    "Content-Length",
+    "Content-Type",
+    "Cookie",
+    "Date",
+    "ETag",
+    "Expect",
+    "Forwarded",
+    "From",
+    "Host",
+    "If-Match",
+    "If-Modified-Since",
+    "If-None-Match",
+    "If-Range",
+    "If-Unmodified-Since",
+    "Origin",
+    "Pragma",
+    "Range",
+    "Referer",
+    "TE",
+    "Upgrade",
+    "User-Agent",
+    "Via",
+    "X-API-Key",
+    "X-Correlation-ID",
+    "X-Forwarded-For",
+    "X-Forwarded-Host",
+    "X-Forwarded-Proto",
+    "X-Request-ID",
+    "X-Requested-With",
+];
+
+const MEDIA_TYPES: &[&str] = &[
+    "application/json",
+    "application/xml",
+    "application/x-www-form-urlencoded",
+    "application/octet-stream",
+    "multipart/form-data",
+    "text/plain",
+    "text/html",
+    "text/xml",
+    "text/event-stream",
+    "*/*",
+];
+
+pub fn header_value_hints(name: &str) -> &'static [&'static str] {
+    match name.to_ascii_lowercase().as_str() {
+        "accept" | "content-type" => MEDIA_TYPES,
+        "accept-encoding" | "content-encoding" => &["gzip", "deflate", "br", "identity", "*"],
+        "accept-language" | "content-language" => &["en-US", "en", "ko-KR", "ko", "*"],
+        "authorization" => &["Bearer ", "Basic "],
+        "cache-control" => &[
+            "no-cache",
+            "no-store",
+            "max-age=0",
+            "must-revalidate",
+            "public",
+            "private",
+        ],
+        "connection" => &["keep-alive", "close"],
+        "pragma" => &["no-cache"],
+        "x-requested-with" => &["XMLHttpRequest"],
+        _ => &[],
+    }
+}
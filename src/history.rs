@@ -0,0 +1,522 @@
+use crate::body::{BodyType, FormPart};
+use crate::{ApiRequest, ApiResponse};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+use tokio::runtime::Runtime;
+
+pub const HISTORY_FILE: &str = "saved_history.json";
+const DEFAULT_LIMIT: usize = 200;
+// 큰 응답 때문에 기록 파일이 커지지 않도록 바디는 앞부분만 남긴다
+const MAX_BODY_LEN: usize = 256 * 1024;
+const TRUNCATED_MARK: &str = "\n... (truncated)";
+
+// 백그라운드 저장 순서. 늦게 끝난 이전 저장이 최신 내용을 덮어쓰지 않도록 한다
+static SAVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+static WRITTEN_GENERATION: Mutex<u64> = Mutex::new(0);
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    // RFC 3339 형식의 보낸 시각
+    pub timestamp: String,
+    pub group: Option<String>,
+    // 복원할 때 쓰는 편집기 상태 (변수 치환 전)
+    pub request: ApiRequest,
+    // 변수와 인증을 적용해 실제로 보낸 값
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    // 실제로 보낸 바디 크기. multipart처럼 알 수 없거나 예전 기록이면 None
+    #[serde(default)]
+    pub body_size: Option<u64>,
+    // 보낸 multipart 파트 (변수 치환 후). 다른 바디 종류에서는 비어 있다
+    #[serde(default)]
+    pub form_data: Vec<FormPart>,
+    pub status: u16,
+    pub response_headers: Vec<(String, String)>,
+    pub response_body: String,
+    pub time_ms: u64,
+}
+
+impl HistoryEntry {
+    pub fn new(
+        group: Option<String>,
+        request: &ApiRequest,
+        sent: &ApiRequest,
+        response: &ApiResponse,
+    ) -> Self {
+        let mut request = request.clone();
+        request.response = None;
+        // 예시 응답은 그룹에 이미 저장되어 있으므로 기록에는 남기지 않는다
+        request.examples.clear();
+        let body = match sent.body_type {
+            BodyType::FormData => format!("<multipart/form-data: {} parts>", sent.form_data.len()),
+            BodyType::Binary => format!("<file: {}>", sent.binary_path),
+            _ => sent.body.clone(),
+        };
+        let body_size = match sent.body_type {
+            BodyType::FormData => None,
+            BodyType::Binary => fs::metadata(&sent.binary_path).ok().map(|meta| meta.len()),
+            _ => Some(sent.body.len() as u64),
+        };
+        let form_data = match sent.body_type {
+            BodyType::FormData => sent.form_data.clone(),
+            _ => Vec::new(),
+        };
+
+        Self {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            group,
+            request,
+            method: sent.method.clone(),
+            url: sent.url.clone(),
+            headers: sent
+                .headers
+                .iter()
+                .filter(|header| !header.key.is_empty())
+                .map(|header| (header.key.clone(), header.value.clone()))
+                .collect(),
+            body: truncate(body),
+            body_size,
+            form_data,
+            status: response.status,
+            response_headers: response
+                .headers
+                .iter()
+                .map(|(key, value)| {
+                    (
+                        key.to_string(),
+                        value.to_str().unwrap_or_default().to_string(),
+                    )
+                })
+                .collect(),
+            response_body: truncate(response.body.clone()),
+            time_ms: response.time_taken.as_millis() as u64,
+        }
+    }
+
+    pub fn host(&self) -> String {
+        reqwest::Url::parse(&self.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    // 목록에 보여줄 "MM-DD HH:MM:SS"
+    pub fn display_time(&self) -> String {
+        chrono::DateTime::parse_from_rfc3339(&self.timestamp)
+            .map(|time| time.format("%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|_| self.timestamp.clone())
+    }
+
+    // 기록된 바디가 보낸 내용 전체인지. 파일과 multipart는 자리 표시 문자열만 남는다
+    pub fn has_full_body(&self) -> bool {
+        match self.request.body_type {
+            BodyType::FormData | BodyType::Binary => false,
+            _ => match self.body_size {
+                Some(size) => size == self.body.len() as u64,
+                None => !self.body.ends_with(TRUNCATED_MARK),
+            },
+        }
+    }
+
+    // 응답을 편집기의 응답 영역에 다시 보여줄 수 있는 형태로 돌려준다
+    pub fn response(&self) -> ApiResponse {
+        saved_response(
+            &self.method,
+            self.status,
+            &self.response_headers,
+            &self.response_body,
+            self.time_ms,
+        )
+    }
+}
+
+// 요청에 붙여 저장해 두는 응답 예시 (HAR에서 가져온 기록된 응답 등)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ResponseExample {
+    pub name: String,
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    pub time_ms: u64,
+}
+
+impl ResponseExample {
+    pub fn new(
+        name: String,
+        status: u16,
+        headers: Vec<(String, String)>,
+        body: String,
+        time_ms: u64,
+    ) -> Self {
+        Self {
+            name,
+            status,
+            headers,
+            body: truncate(body),
+            time_ms,
+        }
+    }
+
+    pub fn response(&self) -> ApiResponse {
+        saved_response("", self.status, &self.headers, &self.body, self.time_ms)
+    }
+}
+
+fn saved_response(
+    method: &str,
+    status: u16,
+    headers: &[(String, String)],
+    body: &str,
+    time_ms: u64,
+) -> ApiResponse {
+    let mut header_map = reqwest::header::HeaderMap::new();
+    for (key, value) in headers {
+        if let (Ok(name), Ok(value)) = (
+            reqwest::header::HeaderName::from_bytes(key.as_bytes()),
+            reqwest::header::HeaderValue::from_str(value),
+        ) {
+            header_map.append(name, value);
+        }
+    }
+    ApiResponse {
+        method: method.to_string(),
+        status,
+        headers: header_map,
+        body: body.to_string(),
+        time_taken: std::time::Duration::from_millis(time_ms),
+        events: Vec::new(),
+    }
+}
+
+fn truncate(mut text: String) -> String {
+    if text.len() > MAX_BODY_LEN {
+        let mut end = MAX_BODY_LEN;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+        text.push_str(TRUNCATED_MARK);
+    }
+    text
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct History {
+    // 최신 항목이 앞에 온다
+    pub entries: Vec<HistoryEntry>,
+    pub limit: usize,
+    // 마지막 저장 이후 바뀌었는지
+    #[serde(skip)]
+    dirty: bool,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            limit: DEFAULT_LIMIT,
+            dirty: false,
+        }
+    }
+}
+
+impl History {
+    // 파일이 없으면 빈 기록, 읽을 수 없으면 덮어쓰지 않도록 에러를 돌려준다
+    pub fn load() -> Result<Self, String> {
+        if let Ok(data) = fs::read_to_string(HISTORY_FILE) {
+            println!("Loading history from file");
+            serde_json::from_str(&data)
+                .map_err(|e| format!("Failed to read {}: {}", HISTORY_FILE, e))
+        } else {
+            println!("No saved history file found");
+            Ok(Self::default())
+        }
+    }
+
+    // 바뀐 내용이 있으면 파일 쓰기는 UI 스레드를 막지 않도록 백그라운드에서 한다
+    pub fn save(&mut self, runtime: &Runtime) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+        let json = match serde_json::to_string_pretty(self) {
+            Ok(json) => json,
+            Err(e) => {
+                println!("Failed to save history: {}", e);
+                return;
+            }
+        };
+        let generation = SAVE_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+        runtime.spawn_blocking(move || {
+            write_latest(&WRITTEN_GENERATION, generation, HISTORY_FILE, &json)
+        });
+    }
+
+    // 다시 읽은 기록을 지금까지 쌓인 기록 뒤에 붙인다. 보관 개수는 파일의 값을 따른다
+    pub fn merge_loaded(&mut self, loaded: History) {
+        self.limit = loaded.limit;
+        self.entries.extend(loaded.entries);
+        self.prune();
+    }
+
+    pub fn push(&mut self, entry: HistoryEntry) {
+        self.entries.insert(0, entry);
+        self.prune();
+    }
+
+    // 보관 개수를 넘는 오래된 항목을 지운다
+    pub fn prune(&mut self) {
+        self.entries.truncate(self.limit);
+        self.dirty = true;
+    }
+
+    pub fn remove(&mut self, idx: usize) {
+        self.entries.remove(idx);
+        self.dirty = true;
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.dirty = true;
+    }
+}
+
+// 이미 더 새로운 내용을 썼으면 건너뛴다
+fn write_latest(written: &Mutex<u64>, generation: u64, path: &str, json: &str) {
+    let mut written = written.lock().unwrap_or_else(PoisonError::into_inner);
+    if *written > generation {
+        return;
+    }
+    if let Err(e) = fs::write(path, json) {
+        println!("Failed to save history: {}", e);
+    }
+    *written = generation;
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum StatusFilter {
+    #[default]
+    All,
+    Success,
+    Redirect,
+    ClientError,
+    ServerError,
+    Failed,
+}
+
+impl StatusFilter {
+    pub const ALL: [StatusFilter; 6] = [
+        StatusFilter::All,
+        StatusFilter::Success,
+        StatusFilter::Redirect,
+        StatusFilter::ClientError,
+        StatusFilter::ServerError,
+        StatusFilter::Failed,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatusFilter::All => "Any status",
+            StatusFilter::Success => "2xx",
+            StatusFilter::Redirect => "3xx",
+            StatusFilter::ClientError => "4xx",
+            StatusFilter::ServerError => "5xx",
+            StatusFilter::Failed => "Failed",
+        }
+    }
+
+    fn matches(&self, status: u16) -> bool {
+        match self {
+            StatusFilter::All => true,
+            StatusFilter::Success => (200..300).contains(&status),
+            StatusFilter::Redirect => (300..400).contains(&status),
+            StatusFilter::ClientError => (400..500).contains(&status),
+            StatusFilter::ServerError => status >= 500,
+            // 연결 실패 등으로 응답을 받지 못한 요청
+            StatusFilter::Failed => status == 0,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct HistoryFilter {
+    pub search: String,
+    pub method: Option<String>,
+    pub status: StatusFilter,
+    pub host: Option<String>,
+}
+
+impl HistoryFilter {
+    pub fn matches(&self, entry: &HistoryEntry) -> bool {
+        let search = self.search.to_lowercase();
+        (search.is_empty()
+            || entry.url.to_lowercase().contains(&search)
+            || entry.request.name.to_lowercase().contains(&search)
+            || entry.body.to_lowercase().contains(&search))
+            && self
+                .method
+                .as_ref()
+                .is_none_or(|method| &entry.method == method)
+            && self.status.matches(entry.status)
+            && self.host.as_ref().is_none_or(|host| &entry.host() == host)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(method: &str, url: &str, status: u16, body: &str) -> HistoryEntry {
+        let sent = ApiRequest {
+            name: "Get users".to_string(),
+            method: method.to_string(),
+            url: url.to_string(),
+            body: body.to_string(),
+            ..Default::default()
+        };
+        let response = ApiResponse {
+            status,
+            ..ApiResponse::error("")
+        };
+        HistoryEntry::new(None, &sent, &sent, &response)
+    }
+
+    #[test]
+    fn push_keeps_newest_within_limit() {
+        let mut history = History {
+            limit: 2,
+            ..Default::default()
+        };
+        for status in [200, 201, 202] {
+            history.push(entry("GET", "https://a.test/", status, ""));
+        }
+        let statuses: Vec<u16> = history.entries.iter().map(|e| e.status).collect();
+        assert_eq!(statuses, vec![202, 201]);
+        assert!(history.dirty);
+
+        history.limit = 1;
+        history.prune();
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(history.entries[0].status, 202);
+    }
+
+    #[test]
+    fn truncate_cuts_on_char_boundary() {
+        // 3바이트 문자가 MAX_BODY_LEN 경계에 걸치도록 한다
+        let text = format!("a{}", "가".repeat(MAX_BODY_LEN / 3 + 1));
+        let truncated = truncate(text);
+        assert!(truncated.ends_with(TRUNCATED_MARK));
+        let kept = &truncated[..truncated.len() - TRUNCATED_MARK.len()];
+        assert!(kept.len() <= MAX_BODY_LEN);
+        assert!(kept.len() > MAX_BODY_LEN - 3);
+
+        assert_eq!(truncate("short".to_string()), "short");
+    }
+
+    #[test]
+    fn has_full_body_detects_truncation_and_placeholders() {
+        assert!(entry("POST", "https://a.test/", 200, "{}").has_full_body());
+        let long = "x".repeat(MAX_BODY_LEN + 1);
+        assert!(!entry("POST", "https://a.test/", 200, &long).has_full_body());
+
+        // 예전 기록은 body_size 없이 표시 문자열로 판단한다
+        let mut old = entry("POST", "https://a.test/", 200, &long);
+        old.body_size = None;
+        assert!(!old.has_full_body());
+        old.body = "{}".to_string();
+        assert!(old.has_full_body());
+
+        let mut form = entry("POST", "https://a.test/", 200, "");
+        form.request.body_type = BodyType::FormData;
+        assert!(!form.has_full_body());
+    }
+
+    #[test]
+    fn filter_matches_search_method_host_and_status() {
+        let ok = entry("GET", "https://api.test/users", 200, "");
+        let post = entry("POST", "https://other.test/items", 404, "{\"Needle\": 1}");
+
+        let mut filter = HistoryFilter::default();
+        assert!(filter.matches(&ok) && filter.matches(&post));
+
+        filter.search = "needle".to_string();
+        assert!(!filter.matches(&ok) && filter.matches(&post));
+        filter.search = "get USERS".to_string();
+        assert!(filter.matches(&ok));
+        filter.search.clear();
+
+        filter.method = Some("POST".to_string());
+        assert!(!filter.matches(&ok) && filter.matches(&post));
+        filter.method = None;
+
+        filter.host = Some("api.test".to_string());
+        assert!(filter.matches(&ok) && !filter.matches(&post));
+        filter.host = None;
+
+        let cases = [
+            (StatusFilter::All, 0, true),
+            (StatusFilter::Success, 204, true),
+            (StatusFilter::Success, 301, false),
+            (StatusFilter::Redirect, 302, true),
+            (StatusFilter::ClientError, 404, true),
+            (StatusFilter::ClientError, 500, false),
+            (StatusFilter::ServerError, 503, true),
+            (StatusFilter::Failed, 0, true),
+            (StatusFilter::Failed, 200, false),
+        ];
+        for (status_filter, status, expected) in cases {
+            filter.status = status_filter;
+            let entry = entry("GET", "https://api.test/", status, "");
+            assert_eq!(
+                filter.matches(&entry),
+                expected,
+                "{} {}",
+                status_filter.label(),
+                status
+            );
+        }
+    }
+
+    #[test]
+    fn merge_loaded_appends_older_entries() {
+        let mut history = History::default();
+        history.push(entry("GET", "https://a.test/new", 200, ""));
+        let mut loaded = History {
+            limit: 2,
+            ..Default::default()
+        };
+        loaded.entries = vec![
+            entry("GET", "https://a.test/old1", 200, ""),
+            entry("GET", "https://a.test/old2", 200, ""),
+        ];
+        history.merge_loaded(loaded);
+        let urls: Vec<&str> = history.entries.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://a.test/new", "https://a.test/old1"]);
+    }
+
+    #[test]
+    fn older_save_does_not_overwrite_newer() {
+        let path =
+            std::env::temp_dir().join(format!("ruquest-history-{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        let written = Mutex::new(0);
+
+        write_latest(&written, 2, path, "new");
+        write_latest(&written, 1, path, "old");
+        assert_eq!(fs::read_to_string(path).unwrap(), "new");
+
+        // 다른 저장이 패닉으로 잠금을 오염시켜도 계속 저장한다
+        let _ = std::panic::catch_unwind(|| {
+            let _guard = written.lock().unwrap();
+            panic!("poison");
+        });
+        assert!(written.is_poisoned());
+        write_latest(&written, 3, path, "newest");
+        assert_eq!(fs::read_to_string(path).unwrap(), "newest");
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,154 @@
+use crate::encoding::hex;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use reqwest::{Method, Url};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const TEMPLATE_HELP: &str =
+    "Placeholders: {method} {path} {host} {timestamp} {body} {body_sha256}";
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum SignatureEncoding {
+    #[default]
+    Hex,
+    Base64,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HmacConfig {
+    pub secret: String,
+    pub header_name: String,
+    // 비워두면 타임스탬프 헤더를 보내지 않는다
+    pub timestamp_header: String,
+    pub template: String,
+    pub encoding: SignatureEncoding,
+}
+
+impl Default for HmacConfig {
+    fn default() -> Self {
+        Self {
+            secret: String::new(),
+            header_name: "X-Signature".to_string(),
+            timestamp_header: "X-Timestamp".to_string(),
+            template: "{method}\n{path}\n{timestamp}\n{body}".to_string(),
+            encoding: SignatureEncoding::Hex,
+        }
+    }
+}
+
+// 템플릿으로 서명할 문자열을 만들고 (헤더 이름, 값) 목록을 반환
+pub fn sign(
+    config: &HmacConfig,
+    method: &Method,
+    url: &Url,
+    body: &[u8],
+    timestamp: u64,
+) -> Result<Vec<(String, String)>, String> {
+    if config.header_name.is_empty() {
+        return Err("HMAC signature header name is empty".to_string());
+    }
+
+    let path = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+    let body_hash = hex(&Sha256::digest(body));
+    let message = config
+        .template
+        .replace("\\n", "\n")
+        .replace("{method}", method.as_str())
+        .replace("{path}", &path)
+        .replace("{host}", url.host_str().unwrap_or_default())
+        .replace("{timestamp}", &timestamp.to_string())
+        .replace("{body_sha256}", &body_hash)
+        .replace("{body}", &String::from_utf8_lossy(body));
+
+    let mut mac = HmacSha256::new_from_slice(config.secret.as_bytes())
+        .map_err(|e| format!("Invalid HMAC secret: {}", e))?;
+    mac.update(message.as_bytes());
+    let signature = mac.finalize().into_bytes();
+    let signature = match config.encoding {
+        SignatureEncoding::Hex => hex(&signature),
+        SignatureEncoding::Base64 => STANDARD.encode(signature),
+    };
+
+    let mut headers = vec![(config.header_name.clone(), signature)];
+    if !config.timestamp_header.is_empty() {
+        headers.push((config.timestamp_header.clone(), timestamp.to_string()));
+    }
+    Ok(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &[u8] = br#"{"m":"{method}"}"#;
+
+    fn sign_default(encoding: SignatureEncoding) -> Vec<(String, String)> {
+        let config = HmacConfig {
+            secret: "secret".to_string(),
+            encoding,
+            ..Default::default()
+        };
+        let url = Url::parse("https://api.example.com/v1/items?a=1").unwrap();
+        sign(&config, &Method::POST, &url, BODY, 1700000000).unwrap()
+    }
+
+    #[test]
+    fn signs_default_template_as_hex() {
+        // 바디 안의 {method}는 치환하지 않는다
+        assert_eq!(
+            sign_default(SignatureEncoding::Hex),
+            vec![
+                (
+                    "X-Signature".to_string(),
+                    "a0a770de49144f8144631cc18361cb79ab0ad1b98776867fff134bdc69bb26a6".to_string()
+                ),
+                ("X-Timestamp".to_string(), "1700000000".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn signs_default_template_as_base64() {
+        assert_eq!(
+            sign_default(SignatureEncoding::Base64)[0].1,
+            "oKdw3kkUT4FEYxzBg2HLeasK0bmHdoZ//xNL3Gm7JqY="
+        );
+    }
+
+    #[test]
+    fn fills_host_body_hash_and_escaped_newline() {
+        let config = HmacConfig {
+            secret: "k".to_string(),
+            header_name: "X-Sig".to_string(),
+            timestamp_header: String::new(),
+            template: r"{host}|{body_sha256}\n{method}".to_string(),
+            encoding: SignatureEncoding::Hex,
+        };
+        let url = Url::parse("https://api.example.com/").unwrap();
+        assert_eq!(
+            sign(&config, &Method::POST, &url, BODY, 0).unwrap(),
+            vec![(
+                "X-Sig".to_string(),
+                "98689bc8f2e715d37fcfaa033c43d82b6fad2730c8fdde2f05237a0b737c0d0f".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn rejects_empty_header_name() {
+        let config = HmacConfig {
+            header_name: String::new(),
+            ..Default::default()
+        };
+        let url = Url::parse("https://api.example.com/").unwrap();
+        assert!(sign(&config, &Method::GET, &url, b"", 0).is_err());
+    }
+}
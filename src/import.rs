@@ -0,0 +1,67 @@
+use crate::ApiRequest;
+use serde_json::Value;
+
+// 가져오기(Postman, OpenAPI, HAR)에서 함께 쓰는 도우미
+
+// 이미 쓰인 이름이면 " (2)", " (3)"처럼 번호를 붙인다
+pub fn unique_name(name: &str, taken: impl Fn(&str) -> bool) -> String {
+    let mut unique = name.to_string();
+    let mut n = 2;
+    while taken(&unique) {
+        unique = format!("{} ({})", name, n);
+        n += 1;
+    }
+    unique
+}
+
+// 그룹 안에서 요청은 이름으로 찾으므로 겹치는 이름에 번호를 붙인다
+pub fn unique_request_name(requests: &[ApiRequest], name: &str) -> String {
+    unique_name(name, |unique| {
+        requests.iter().any(|request| request.name == unique)
+    })
+}
+
+// 문자열은 따옴표 없이, null은 빈 값으로, 나머지는 JSON 그대로 옮긴다
+pub fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+pub fn str_field(value: &Value, key: &str) -> String {
+    value.get(key).map(scalar).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn unique_name_numbers_duplicates() {
+        let requests = vec![
+            ApiRequest {
+                name: "List".to_string(),
+                ..Default::default()
+            },
+            ApiRequest {
+                name: "List (2)".to_string(),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(unique_request_name(&requests, "List"), "List (3)");
+        assert_eq!(unique_request_name(&requests, "Create"), "Create");
+    }
+
+    #[test]
+    fn str_field_stringifies_scalars() {
+        let value = json!({ "text": "a", "number": 3, "empty": null });
+        assert_eq!(str_field(&value, "text"), "a");
+        assert_eq!(str_field(&value, "number"), "3");
+        assert_eq!(str_field(&value, "empty"), "");
+        assert_eq!(str_field(&value, "missing"), "");
+        assert_eq!(str_field(&Value::Null, "text"), "");
+    }
+}
@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonRpcCall {
+    pub enabled: bool,
+    pub method: String,
+    // JSON 배열이나 객체 텍스트. 비어 있으면 params를 보내지 않는다
+    pub params: String,
+    // 알림(notification)은 id 없이 보내며 응답을 기다리지 않는다
+    pub notification: bool,
+}
+
+impl Default for JsonRpcCall {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            method: String::new(),
+            params: String::new(),
+            notification: false,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonRpcRequest {
+    pub calls: Vec<JsonRpcCall>,
+    // 다음 요청에 붙일 id. 보낼 때마다 증가한다
+    pub next_id: u64,
+    // 마지막으로 보낸 (id, method). 응답을 id로 맞춰 보여줄 때 쓴다
+    #[serde(skip)]
+    pub sent: Vec<(u64, String)>,
+}
+
+impl Default for JsonRpcRequest {
+    fn default() -> Self {
+        Self {
+            calls: vec![JsonRpcCall::default()],
+            next_id: 1,
+            sent: Vec::new(),
+        }
+    }
+}
+
+impl JsonRpcRequest {
+    // 활성화된 호출에 id를 붙여 요청 바디를 만든다. 둘 이상이면 배치 배열로 보낸다
+    pub fn body(&mut self) -> Result<String, String> {
+        let mut messages = Vec::new();
+        self.sent.clear();
+
+        for call in self.calls.iter().filter(|call| call.enabled) {
+            if call.method.trim().is_empty() {
+                return Err("JSON-RPC method is empty".to_string());
+            }
+            let mut message = json!({ "jsonrpc": "2.0", "method": call.method });
+            if !call.params.trim().is_empty() {
+                let params: Value = serde_json::from_str(&call.params)
+                    .map_err(|e| format!("Invalid params for '{}': {}", call.method, e))?;
+                if !params.is_array() && !params.is_object() {
+                    return Err(format!(
+                        "Params for '{}' must be an array or an object",
+                        call.method
+                    ));
+                }
+                message["params"] = params;
+            }
+            if !call.notification {
+                message["id"] = json!(self.next_id);
+                self.sent.push((self.next_id, call.method.clone()));
+                self.next_id += 1;
+            }
+            messages.push(message);
+        }
+
+        let body = match messages.len() {
+            0 => return Err("No JSON-RPC calls are enabled".to_string()),
+            1 => messages.remove(0),
+            _ => Value::Array(messages),
+        };
+        serde_json::to_string_pretty(&body).map_err(|e| e.to_string())
+    }
+}
+
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    pub data: Option<Value>,
+}
+
+pub struct RpcResponse {
+    pub id: Value,
+    pub method: Option<String>,
+    pub outcome: Result<Value, RpcError>,
+}
+
+// 응답 객체(배치면 배열)를 보낸 요청과 id로 맞춘다.
+// 응답을 받지 못한 id는 두 번째 값으로 돌려준다
+pub fn match_responses(body: &Value, sent: &[(u64, String)]) -> (Vec<RpcResponse>, Vec<u64>) {
+    let items = match body {
+        Value::Array(items) => items.as_slice(),
+        item => std::slice::from_ref(item),
+    };
+    let empty = Map::new();
+
+    let mut responses: Vec<RpcResponse> = items
+        .iter()
+        .map(|item| {
+            let object = item.as_object().unwrap_or(&empty);
+            let id = object.get("id").cloned().unwrap_or(Value::Null);
+            let method = id.as_u64().and_then(|id| {
+                sent.iter()
+                    .find(|(sent_id, _)| *sent_id == id)
+                    .map(|(_, method)| method.clone())
+            });
+            let outcome = match object.get("error") {
+                Some(error) => Err(RpcError {
+                    code: error
+                        .get("code")
+                        .and_then(Value::as_i64)
+                        .unwrap_or_default(),
+                    message: error
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    data: error.get("data").cloned(),
+                }),
+                None => Ok(object.get("result").cloned().unwrap_or(Value::Null)),
+            };
+            RpcResponse {
+                id,
+                method,
+                outcome,
+            }
+        })
+        .collect();
+
+    // 배치 응답은 순서가 보장되지 않으므로 보낸 순서로 정렬한다
+    responses.sort_by_key(|response| {
+        sent.iter()
+            .position(|(id, _)| response.id.as_u64() == Some(*id))
+            .unwrap_or(usize::MAX)
+    });
+    let missing = sent
+        .iter()
+        .map(|(id, _)| *id)
+        .filter(|id| {
+            !responses
+                .iter()
+                .any(|response| response.id.as_u64() == Some(*id))
+        })
+        .collect();
+    (responses, missing)
+}
+
+// 표준 오류 코드의 이름
+pub fn error_name(code: i64) -> Option<&'static str> {
+    match code {
+        -32700 => Some("Parse error"),
+        -32600 => Some("Invalid Request"),
+        -32601 => Some("Method not found"),
+        -32602 => Some("Invalid params"),
+        -32603 => Some("Internal error"),
+        -32099..=-32000 => Some("Server error"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sent() -> Vec<(u64, String)> {
+        vec![
+            (1, "sum".to_string()),
+            (2, "subtract".to_string()),
+            (3, "get_data".to_string()),
+        ]
+    }
+
+    #[test]
+    fn orders_batch_responses_by_sent_id() {
+        let body = json!([
+            {"jsonrpc": "2.0", "id": 3, "result": ["hello", 5]},
+            {"jsonrpc": "2.0", "id": 1, "result": 7},
+            {"jsonrpc": "2.0", "id": 2, "error": {"code": -32601, "message": "Method not found", "data": "subtract"}}
+        ]);
+        let (responses, missing) = match_responses(&body, &sent());
+        assert!(missing.is_empty());
+
+        let ids: Vec<Value> = responses.iter().map(|r| r.id.clone()).collect();
+        assert_eq!(ids, vec![json!(1), json!(2), json!(3)]);
+        assert_eq!(responses[0].method.as_deref(), Some("sum"));
+        assert_eq!(responses[0].outcome.as_ref().ok(), Some(&json!(7)));
+
+        let Err(error) = &responses[1].outcome else {
+            panic!("expected an error response");
+        };
+        assert_eq!(responses[1].method.as_deref(), Some("subtract"));
+        assert_eq!(error.code, -32601);
+        assert_eq!(error.message, "Method not found");
+        assert_eq!(error.data, Some(json!("subtract")));
+        assert_eq!(error_name(error.code), Some("Method not found"));
+    }
+
+    #[test]
+    fn reports_missing_and_unknown_ids() {
+        // 단일 응답 객체, 모르는 id, id가 null인 파싱 오류
+        let (responses, missing) =
+            match_responses(&json!({"jsonrpc": "2.0", "id": 2, "result": null}), &sent());
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].method.as_deref(), Some("subtract"));
+        assert_eq!(missing, vec![1, 3]);
+
+        let body = json!([
+            {"jsonrpc": "2.0", "id": null, "error": {"code": -32700, "message": "Parse error"}},
+            {"jsonrpc": "2.0", "id": 9, "result": 1},
+            {"jsonrpc": "2.0", "id": 1, "result": 2}
+        ]);
+        let (responses, missing) = match_responses(&body, &sent());
+        assert_eq!(missing, vec![2, 3]);
+        // 보낸 id가 먼저 오고, 맞지 않는 응답은 뒤에 남는다
+        assert_eq!(responses[0].id, json!(1));
+        assert!(responses[1..].iter().all(|r| r.method.is_none()));
+        assert_eq!(responses.len(), 3);
+    }
+
+    #[test]
+    fn body_assigns_ids_and_skips_notifications() {
+        let mut request = JsonRpcRequest {
+            calls: vec![
+                JsonRpcCall {
+                    method: "sum".to_string(),
+                    params: "[1, 2]".to_string(),
+                    ..Default::default()
+                },
+                JsonRpcCall {
+                    method: "notify".to_string(),
+                    notification: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let body: Value = serde_json::from_str(&request.body().unwrap()).unwrap();
+        assert_eq!(body[0]["id"], json!(1));
+        assert_eq!(body[0]["params"], json!([1, 2]));
+        assert!(body[1].get("id").is_none());
+        assert_eq!(request.sent, vec![(1, "sum".to_string())]);
+        assert_eq!(request.next_id, 2);
+
+        request.calls[0].params = "3".to_string();
+        assert!(request.body().is_err());
+    }
+}
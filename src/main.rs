@@ -1,48 +1,237 @@
+mod auth;
+mod body;
+mod curl;
+mod digest;
+mod encoding;
+mod environment;
+mod graphql;
+mod grpc;
+mod har;
+mod headers;
+mod history;
+mod hmac_signature;
+mod import;
+mod jsonrpc;
+mod oauth2;
+mod openapi;
+mod params;
+mod postman;
+mod sigv4;
+mod sse;
+mod websocket;
+
+use auth::{render_auth, set_header, Auth};
+use body::{
+    decode_form, encode_form, form_table_to_text, BodyType, FormPart, FormPartKind, RequestBody,
+};
 use eframe::egui;
 use egui::{Color32, Context, RichText, ScrollArea, Ui};
+use environment::{variable_layout_job, Environment, EnvironmentStore, Resolver};
+use futures_util::{StreamExt, TryStreamExt};
+use graphql::{GraphQlRequest, Schema};
+use grpc::{GrpcEvent, GrpcRequest};
+use headers::{header_value_hints, HEADER_NAMES};
+use history::{History, HistoryEntry, HistoryFilter, ResponseExample, StatusFilter};
+use jsonrpc::{JsonRpcCall, JsonRpcRequest};
+use oauth2::OAuth2Token;
+use params::{
+    encode_query, parse_query, split_url, substitute_path_params, sync_key_values,
+    sync_params_from_url, sync_path_params, url_with_params, KeyValue,
+};
 use reqwest::{
-    header::{HeaderMap, HeaderName},
-    Client, Method,
+    header::{
+        HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE,
+        WWW_AUTHENTICATE,
+    },
+    Body, Client, Method, RequestBuilder, StatusCode,
 };
-use rfd::FileDialog;
+use rfd::FileDialog; // 파일 다이얼로그를 위한 크레이트 추가
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sse::SseEvent;
+use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::time::Duration;
-use tokio::runtime::Runtime; // 파일 다이얼로그를 위한 크레이트 추가
-                             // Request 액션을 위한 enum 추가
+use tokio::runtime::Runtime;
+use tokio_util::io::ReaderStream;
+use tokio_util::sync::CancellationToken;
+use websocket::{Direction, WebSocketConfig, WebSocketEvent};
+
+// Request 액션을 위한 enum 추가
 #[derive(Clone)]
 enum RequestAction {
     Add,
-    Select(ApiRequest),
+    Select,
     Delete,
 }
 #[derive(Clone, Default, Serialize, Deserialize)]
 struct RequestGroup {
     name: String,
     requests: Vec<ApiRequest>,
+    #[serde(default)]
+    variables: Vec<(String, String)>,
+    #[serde(default)]
+    headers: Vec<KeyValue>,
+    #[serde(default)]
+    auth: Auth,
     #[serde(skip)]
     is_expanded: bool,
 }
 
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+enum RequestKind {
+    #[default]
+    Http,
+    GraphQl,
+    WebSocket,
+    Grpc,
+    JsonRpc,
+}
+
+impl RequestKind {
+    const ALL: [RequestKind; 5] = [
+        RequestKind::Http,
+        RequestKind::GraphQl,
+        RequestKind::WebSocket,
+        RequestKind::Grpc,
+        RequestKind::JsonRpc,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            RequestKind::Http => "HTTP",
+            RequestKind::GraphQl => "GraphQL",
+            RequestKind::WebSocket => "WebSocket",
+            RequestKind::Grpc => "gRPC",
+            RequestKind::JsonRpc => "JSON-RPC",
+        }
+    }
+}
+
 #[derive(Clone, Default, Serialize, Deserialize)]
 struct ApiRequest {
     name: String, // API 별칭
+    #[serde(default)]
+    kind: RequestKind,
     url: String,
     method: String,
-    headers: Vec<(String, String)>,
+    headers: Vec<KeyValue>,
+    #[serde(default)]
+    body_type: BodyType,
     body: String,
+    #[serde(default)]
+    form_data: Vec<FormPart>,
+    #[serde(default)]
+    url_encoded: Vec<KeyValue>,
+    #[serde(default)]
+    binary_path: String,
+    // true면 x-www-form-urlencoded 바디를 표 대신 원문으로 편집한다
+    #[serde(default)]
+    url_encoded_raw: bool,
+    #[serde(default)]
+    params: Vec<KeyValue>,
+    #[serde(default)]
+    path_params: Vec<KeyValue>,
+    #[serde(default)]
+    variables: Vec<(String, String)>,
+    #[serde(default)]
+    auth: Auth,
+    #[serde(default)]
+    graphql: GraphQlRequest,
+    #[serde(default)]
+    websocket: WebSocketConfig,
+    #[serde(default)]
+    grpc: GrpcRequest,
+    #[serde(default)]
+    jsonrpc: JsonRpcRequest,
+    // 이벤트 스트림이 끊기면 Last-Event-ID를 붙여 다시 연결한다
+    #[serde(default)]
+    sse_reconnect: bool,
+    // 자체 서명 인증서 등을 쓰는 서버를 위해 TLS 인증서 검증을 건너뛴다 (curl -k)
+    #[serde(default)]
+    insecure: bool,
+    // 저장해 둔 응답 예시. 선택하면 응답 영역에 보여준다
+    #[serde(default)]
+    examples: Vec<ResponseExample>,
     #[serde(skip)]
     response: Option<ApiResponse>,
 }
 
+impl ApiRequest {
+    // 사이드바 목록에 이름 옆에 붙는 표시
+    fn list_label(&self) -> String {
+        match self.kind {
+            RequestKind::Http => self.method.clone(),
+            kind => kind.label().to_string(),
+        }
+    }
+}
+
+// 바디 편집기를 숨기고 바디도 보내지 않는 메소드
+const BODYLESS_METHODS: [&str; 3] = ["GET", "HEAD", "TRACE"];
+const GROUPS_FILE: &str = "saved_groups.json";
+
 #[derive(Clone)]
 struct ApiResponse {
+    // 응답을 받은 요청의 메소드. HEAD 응답은 바디 대신 안내를 보여준다
+    method: String,
     status: u16,
     headers: HeaderMap,
     body: String,
     time_taken: Duration,
+    // text/event-stream 응답에서 받은 이벤트
+    events: Vec<SseEvent>,
+}
+
+impl ApiResponse {
+    // 요청을 보내지 못했을 때 응답 영역에 보여줄 오류
+    fn error(message: impl std::fmt::Display) -> Self {
+        Self {
+            method: String::new(),
+            status: 0,
+            headers: HeaderMap::new(),
+            body: format!("Error: {}", message),
+            time_taken: Duration::from_secs(0),
+            events: Vec::new(),
+        }
+    }
+}
+
+// 비동기 작업에서 UI로 전달되는 이벤트
+enum ApiEvent {
+    Response(ApiResponse),
+    UploadProgress {
+        sent: u64,
+        total: u64,
+    },
+    OAuthToken(TokenTarget, OAuth2Token),
+    OAuthError(String),
+    GraphQlSchema {
+        url: String,
+        result: Result<Schema, String>,
+    },
+    WebSocket(u64, WebSocketEvent),
+    GrpcDescriptors {
+        name: String,
+        result: Result<prost_reflect::DescriptorPool, String>,
+    },
+    Grpc(u64, GrpcEvent),
+    EventStreamOpened(ApiResponse, CancellationToken),
+    EventStreamEvent(SseEvent),
+    EventStreamStatus(String),
+    EventStreamClosed(String),
+}
+
+// 발급받은 OAuth2 토큰을 저장할 위치
+#[derive(Clone)]
+enum TokenTarget {
+    Request {
+        group_index: Option<usize>,
+        name: String,
+    },
+    Group(usize),
 }
 
 #[derive(Default)]
@@ -58,24 +247,88 @@ struct NewGroupDialog {
     name: String,
 }
 
+#[derive(Default)]
+struct GroupDialog {
+    show: bool,
+    group_index: Option<usize>,
+}
+
+#[derive(Default)]
+struct HarDialog {
+    show: bool,
+    // 기록된 응답을 요청의 예시로 붙인다
+    attach_responses: bool,
+}
+
+// 붙여 넣은 curl 명령을 그룹의 새 요청으로 가져오는 다이얼로그
+#[derive(Default)]
+struct CurlDialog {
+    show: bool,
+    group_index: Option<usize>,
+    command: String,
+    error: Option<String>,
+}
+
+#[derive(Default)]
+struct EnvironmentDialog {
+    show: bool,
+    name: String,
+    selected: Option<usize>,
+}
+
 struct ApiTester {
     groups: Vec<RequestGroup>,
     current_request: ApiRequest,
     methods: Vec<String>,
-    tx: Sender<ApiResponse>,
-    rx: Receiver<ApiResponse>,
+    tx: Sender<ApiEvent>,
+    rx: Receiver<ApiEvent>,
     is_loading: bool,
     runtime: Runtime,
     new_request_dialog: NewRequestDialog,
     new_group_dialog: NewGroupDialog,
+    group_dialog: GroupDialog,
+    environment_dialog: EnvironmentDialog,
+    curl_dialog: CurlDialog,
+    har_dialog: HarDialog,
+    environments: EnvironmentStore,
+    oauth_status: Option<String>,
+    upload_progress: Option<(u64, u64)>,
+    graphql_status: Option<String>,
+    websocket: websocket::Session,
+    grpc: grpc::Session,
+    grpc_status: Option<String>,
+    // Copy as cURL 결과
+    curl_status: Option<String>,
+    event_stream: Option<CancellationToken>,
+    event_stream_status: Option<String>,
+    history: History,
+    // 기록 파일을 읽지 못한 이유. 해결하기 전까지 기록을 저장하지 않는다
+    history_error: Option<String>,
+    history_filter: HistoryFilter,
+    show_history: bool,
+    // 응답을 기다리는 요청의 (그룹 이름, 편집기 상태, 실제로 보낸 요청)
+    history_pending: Option<(Option<String>, ApiRequest, ApiRequest)>,
+    history_target_group: Option<usize>,
+    // 그룹 파일을 읽지 못한 이유. 해결하기 전까지 그룹을 저장하지 않는다
+    groups_error: Option<String>,
     import_error: Option<String>, // 추가된 필드
-
+    // 가져오기/내보내기 중 건너뛴 항목
+    import_warnings: Vec<String>,
 }
 impl Default for ApiTester {
     fn default() -> Self {
         let (tx, rx) = channel();
+        let (groups, groups_error) = match Self::load_groups() {
+            Ok(groups) => (groups, None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+        let (history, history_error) = match History::load() {
+            Ok(history) => (history, None),
+            Err(e) => (History::default(), Some(e)),
+        };
         Self {
-            groups: Self::load_groups(),
+            groups,
+            groups_error,
             current_request: ApiRequest::default(),
             methods: vec![
                 "GET".to_string(),
@@ -83,6 +336,9 @@ impl Default for ApiTester {
                 "PUT".to_string(),
                 "DELETE".to_string(),
                 "PATCH".to_string(),
+                "HEAD".to_string(),
+                "OPTIONS".to_string(),
+                "TRACE".to_string(),
             ],
             tx,
             rx,
@@ -90,30 +346,78 @@ impl Default for ApiTester {
             runtime: Runtime::new().expect("Failed to create Tokio runtime"),
             new_request_dialog: NewRequestDialog::default(),
             new_group_dialog: NewGroupDialog::default(),
+            group_dialog: GroupDialog::default(),
+            environment_dialog: EnvironmentDialog::default(),
+            curl_dialog: CurlDialog::default(),
+            har_dialog: HarDialog::default(),
+            environments: EnvironmentStore::load(),
+            oauth_status: None,
+            upload_progress: None,
+            graphql_status: None,
+            websocket: websocket::Session::default(),
+            grpc: grpc::Session::default(),
+            grpc_status: None,
+            curl_status: None,
+            event_stream: None,
+            event_stream_status: None,
+            history,
+            history_error,
+            history_filter: HistoryFilter::default(),
+            show_history: false,
+            history_pending: None,
+            history_target_group: None,
             import_error: None,
+            import_warnings: Vec::new(),
         }
     }
 }
 
 impl ApiTester {
-    fn import_group(&mut self, file_path: &str) -> Result<(), String> {
+    // Ruquest 그룹 JSON, Postman 컬렉션, OpenAPI/Swagger 명세(JSON 또는 YAML)를 가져온다.
+    // 가져오며 생긴 경고를 반환한다
+    fn import_group(&mut self, file_path: &Path) -> Result<Vec<String>, String> {
         let content =
             fs::read_to_string(file_path).map_err(|e| format!("Failed to read file: {}", e))?;
 
-        let imported_group: RequestGroup =
-            serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+        // YAML 파일이거나 JSON 객체로 시작하지 않으면 YAML 오류를 보여준다
+        let yaml = file_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"))
+            || !content.trim_start().starts_with('{');
+        let value: Value = match serde_json::from_str(&content) {
+            Ok(value) => value,
+            Err(json_error) => match serde_yaml::from_str(&content) {
+                Ok(value) => value,
+                Err(yaml_error) if yaml => {
+                    return Err(format!("Failed to parse YAML: {}", yaml_error))
+                }
+                Err(_) => return Err(format!("Failed to parse JSON: {}", json_error)),
+            },
+        };
+        let (imported_groups, warnings) = if postman::is_collection(&value) {
+            postman::import(&value)?
+        } else if openapi::is_spec(&value) {
+            openapi::import(&value)?
+        } else {
+            let group: RequestGroup = serde_json::from_value(value)
+                .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+            (vec![group], Vec::new())
+        };
 
         // 중복 검사
-        if self.groups.iter().any(|g| g.name == imported_group.name) {
-            return Err(format!("Group '{}' already exists", imported_group.name));
+        for group in &imported_groups {
+            if self.groups.iter().any(|g| g.name == group.name) {
+                return Err(format!("Group '{}' already exists", group.name));
+            }
         }
 
-        self.groups.push(imported_group);
+        self.groups.extend(imported_groups);
         self.save_groups();
-        Ok(())
+        Ok(warnings)
     }
 
-    fn export_group(&self, group_idx: usize, file_path: &str) -> Result<(), String> {
+    fn export_group(&self, group_idx: usize, file_path: &Path) -> Result<(), String> {
         if let Some(group) = self.groups.get(group_idx) {
             let json = serde_json::to_string_pretty(group)
                 .map_err(|e| format!("Failed to serialize group: {}", e))?;
@@ -126,46 +430,146 @@ impl ApiTester {
         }
     }
 
-    fn load_groups() -> Vec<RequestGroup> {
-        if let Ok(data) = fs::read_to_string("saved_groups.json") {
+    // Postman Collection v2.1로 내보낸다. 내보내지 못한 항목에 대한 경고를 반환한다
+    fn export_group_postman(
+        &self,
+        group_idx: usize,
+        file_path: &Path,
+    ) -> Result<Vec<String>, String> {
+        let group = self.groups.get(group_idx).ok_or("Group not found")?;
+        let (collection, warnings) = postman::export(group);
+        let json = serde_json::to_string_pretty(&collection)
+            .map_err(|e| format!("Failed to serialize collection: {}", e))?;
+        fs::write(file_path, json).map_err(|e| format!("Failed to write file: {}", e))?;
+        Ok(warnings)
+    }
+
+    // HAR 캡처를 파일 이름의 그룹으로 가져온다. 이름이 겹치면 번호를 붙인다
+    fn import_har(
+        &mut self,
+        file_path: &Path,
+        attach_responses: bool,
+    ) -> Result<Vec<String>, String> {
+        let content =
+            fs::read_to_string(file_path).map_err(|e| format!("Failed to read file: {}", e))?;
+        let value: Value =
+            serde_json::from_str(&content).map_err(|e| format!("Failed to parse HAR: {}", e))?;
+        if !har::is_har(&value) {
+            return Err("Not a HAR file: missing log.entries".to_string());
+        }
+
+        let stem = file_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("HAR");
+        let name = import::unique_name(stem, |name| {
+            self.groups.iter().any(|group| group.name == name)
+        });
+
+        let (group, warnings) = har::import(&value, &name, attach_responses)?;
+        self.groups.push(group);
+        self.save_groups();
+        Ok(warnings)
+    }
+
+    // 그룹의 각 요청에 대한 가장 최근 기록을 HAR로 내보낸다
+    fn export_group_har(&self, group_idx: usize, file_path: &Path) -> Result<Vec<String>, String> {
+        let group = self.groups.get(group_idx).ok_or("Group not found")?;
+        let mut entries = Vec::new();
+        let mut warnings = Vec::new();
+        for request in &group.requests {
+            match self.history.entries.iter().find(|entry| {
+                entry.group.as_deref() == Some(group.name.as_str())
+                    && entry.request.name == request.name
+            }) {
+                Some(entry) => entries.push(entry),
+                None => warnings.push(format!(
+                    "'{}' has no recorded response in history and was skipped",
+                    request.name
+                )),
+            }
+        }
+        write_har(&entries, file_path)?;
+        Ok(warnings)
+    }
+
+    // 현재 필터에 맞는 기록을 오래된 순서로 HAR에 담는다
+    fn export_history_har(&self, file_path: &Path) -> Result<(), String> {
+        let entries: Vec<&HistoryEntry> = self
+            .history
+            .entries
+            .iter()
+            .rev()
+            .filter(|entry| self.history_filter.matches(entry))
+            .collect();
+        write_har(&entries, file_path)
+    }
+
+    // 파일이 없으면 빈 목록, 읽을 수 없으면 덮어쓰지 않도록 에러를 돌려준다
+    fn load_groups() -> Result<Vec<RequestGroup>, String> {
+        if let Ok(data) = fs::read_to_string(GROUPS_FILE) {
             println!("Loading groups from file");
-            serde_json::from_str(&data).unwrap_or_default()
+            serde_json::from_str(&data)
+                .map_err(|e| format!("Failed to read {}: {}", GROUPS_FILE, e))
         } else {
             println!("No saved groups file found");
-            Vec::new()
+            Ok(Vec::new())
         }
     }
 
     fn save_groups(&self) {
+        if self.groups_error.is_some() {
+            println!("Not saving groups until {} is resolved", GROUPS_FILE);
+            return;
+        }
         if let Ok(json) = serde_json::to_string_pretty(&self.groups) {
-            if let Err(e) = fs::write("saved_groups.json", json) {
+            if let Err(e) = fs::write(GROUPS_FILE, json) {
                 println!("Failed to save groups: {}", e);
             }
         }
     }
 
-    fn render_headers(&mut self, ui: &mut Ui) {
-        let mut headers_to_remove = Vec::new();
-
-        for (idx, (key, value)) in self.current_request.headers.iter_mut().enumerate() {
-            ui.horizontal(|ui| {
-                ui.text_edit_singleline(key);
-                ui.text_edit_singleline(value);
-                if ui.button("❌").clicked() {
-                    headers_to_remove.push(idx);
+    // 현재 요청을 그룹에 반영하고 저장
+    fn save_current_request(&mut self) {
+        if let Some(group_idx) = self.new_request_dialog.group_index {
+            if group_idx < self.groups.len() {
+                // 현재 요청 업데이트
+                for request in &mut self.groups[group_idx].requests {
+                    if request.name == self.current_request.name {
+                        *request = self.current_request.clone();
+                        self.save_groups();
+                        break;
+                    }
                 }
-            });
+            }
         }
+    }
 
-        for idx in headers_to_remove.iter().rev() {
-            self.current_request.headers.remove(*idx);
-        }
+    fn current_group(&self) -> Option<&RequestGroup> {
+        self.new_request_dialog
+            .group_index
+            .and_then(|idx| self.groups.get(idx))
+    }
 
-        if ui.button("Add Header").clicked() {
-            self.current_request
-                .headers
-                .push((String::new(), String::new()));
-        }
+    fn active_variables(&self) -> HashMap<String, String> {
+        scoped_variables(
+            self.environments.active_environment(),
+            self.current_group(),
+            &self.current_request,
+        )
+    }
+
+    fn resolve_request(&self) -> Result<ApiRequest, String> {
+        resolve_request(
+            &self.current_request,
+            self.current_group(),
+            self.environments.active_environment(),
+        )
+    }
+
+    fn render_headers(&mut self, ui: &mut Ui) {
+        let variables = self.active_variables();
+        render_header_table(ui, &mut self.current_request.headers, &variables);
     }
 
     fn render_response(&self, ui: &mut Ui, response: &ApiResponse) {
@@ -191,6 +595,59 @@ impl ApiTester {
             }
         });
 
+        if response.method == "HEAD" {
+            let length = response
+                .headers
+                .get(CONTENT_LENGTH)
+                .and_then(|value| value.to_str().ok())
+                .map(|length| format!(" (Content-Length: {})", length))
+                .unwrap_or_default();
+            ui.label(RichText::new(format!("HEAD response has no body{}", length)).weak());
+            return;
+        }
+
+        if sse::is_event_stream(&response.headers) {
+            egui::CollapsingHeader::new(format!("Events ({})", response.events.len()))
+                .id_salt("response_events")
+                .default_open(true)
+                .show(ui, |ui| {
+                    ScrollArea::vertical()
+                        .id_salt("response_events_scroll")
+                        .max_height(400.0)
+                        .stick_to_bottom(true)
+                        .show(ui, |ui| {
+                            for event in &response.events {
+                                ui.horizontal_wrapped(|ui| {
+                                    ui.label(RichText::new(&event.timestamp).weak());
+                                    ui.label(RichText::new(&event.event).strong());
+                                    if let Some(id) = &event.id {
+                                        ui.label(RichText::new(format!("id: {}", id)).weak());
+                                    }
+                                    if let Some(retry) = event.retry {
+                                        ui.label(
+                                            RichText::new(format!("retry: {}ms", retry)).weak(),
+                                        );
+                                    }
+                                });
+                                ui.label(&event.data);
+                                ui.separator();
+                            }
+                        });
+                });
+            return;
+        }
+
+        if self.current_request.kind == RequestKind::JsonRpc {
+            if let Ok(json) = serde_json::from_str::<Value>(&response.body) {
+                egui::CollapsingHeader::new("JSON-RPC Responses")
+                    .id_salt("jsonrpc_responses")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        render_jsonrpc_responses(ui, &json, &self.current_request.jsonrpc.sent);
+                    });
+            }
+        }
+
         ui.collapsing("Response Body", |ui| {
             if let Ok(json) = serde_json::from_str::<Value>(&response.body) {
                 ui.label(serde_json::to_string_pretty(&json).unwrap_or_default());
@@ -202,30 +659,84 @@ impl ApiTester {
     fn render_requests_panel(&mut self, ui: &mut Ui) {
         ui.heading("API Groups");
 
+        // 그룹 파일을 읽지 못했으면 사용자가 처리할 때까지 편집을 막는다
+        if let Some(error) = &self.groups_error {
+            ui.label(RichText::new(error).color(Color32::RED));
+            ui.horizontal(|ui| {
+                if ui.button("Retry").clicked() {
+                    match Self::load_groups() {
+                        Ok(groups) => {
+                            self.groups = groups;
+                            self.groups_error = None;
+                        }
+                        Err(e) => self.groups_error = Some(e),
+                    }
+                }
+                if ui.button("Back up and start empty").clicked() {
+                    match back_up_file(GROUPS_FILE) {
+                        Ok(backup) => {
+                            self.groups_error = None;
+                            self.import_warnings =
+                                vec![format!("Unreadable groups were moved to {}", backup)];
+                        }
+                        Err(e) => self.groups_error = Some(e),
+                    }
+                }
+            });
+            return;
+        }
+
         // 상단의 버튼들을 수평으로 배치
         ui.horizontal(|ui| {
             if ui.button("New Group").clicked() {
                 self.new_group_dialog.show = true;
             }
             if ui.button("Import Group").clicked() {
-                if let Some(path) = FileDialog::new().add_filter("JSON", &["json"]).pick_file() {
-                    match self.import_group(path.to_str().unwrap()) {
-                        Ok(_) => self.import_error = None,
-                        Err(e) => self.import_error = Some(e),
+                if let Some(path) = FileDialog::new()
+                    .add_filter("JSON / YAML", &["json", "yaml", "yml"])
+                    .pick_file()
+                {
+                    match self.import_group(&path) {
+                        Ok(warnings) => {
+                            self.import_error = None;
+                            self.import_warnings = warnings;
+                        }
+                        Err(e) => {
+                            self.import_error = Some(e);
+                            self.import_warnings.clear();
+                        }
                     }
                 }
             }
+            if ui.button("Import HAR").clicked() {
+                self.har_dialog = HarDialog {
+                    show: true,
+                    attach_responses: true,
+                };
+            }
         });
 
         // 에러 메시지 표시
         if let Some(error) = &self.import_error {
             ui.label(RichText::new(error).color(Color32::RED));
         }
+        if !self.import_warnings.is_empty() {
+            ui.collapsing(format!("Warnings ({})", self.import_warnings.len()), |ui| {
+                for warning in &self.import_warnings {
+                    ui.label(RichText::new(warning).color(Color32::YELLOW));
+                }
+                if ui.button("Dismiss").clicked() {
+                    self.import_warnings.clear();
+                }
+            });
+        }
 
         ScrollArea::vertical().show(ui, |ui| {
             let mut group_to_delete = None;
             let mut request_action = None;
             let mut export_group_idx = None;
+            let mut export_postman_idx = None;
+            let mut export_har_idx = None;
 
             for (group_idx, group) in self.groups.iter_mut().enumerate() {
                 ui.horizontal(|ui| {
@@ -255,9 +766,23 @@ impl ApiTester {
                             if ui.button("Add API").clicked() {
                                 request_action = Some((group_idx, 0, RequestAction::Add));
                             }
+                            if ui.button("Import cURL").clicked() {
+                                self.curl_dialog.show = true;
+                                self.curl_dialog.group_index = Some(group_idx);
+                            }
+                            if ui.button("Edit Group").clicked() {
+                                self.group_dialog.show = true;
+                                self.group_dialog.group_index = Some(group_idx);
+                            }
                             if ui.button("Export Group").clicked() {
                                 export_group_idx = Some(group_idx);
                             }
+                            if ui.button("Export as Postman").clicked() {
+                                export_postman_idx = Some(group_idx);
+                            }
+                            if ui.button("Export as HAR").clicked() {
+                                export_har_idx = Some(group_idx);
+                            }
                             if ui.button("Delete Group").clicked() {
                                 group_to_delete = Some(group_idx);
                             }
@@ -271,14 +796,11 @@ impl ApiTester {
                         for (req_idx, request) in group.requests.iter().enumerate() {
                             ui.horizontal(|ui| {
                                 if ui
-                                    .button(&format!("{} - {}", request.name, request.method))
+                                    .button(format!("{} - {}", request.name, request.list_label()))
                                     .clicked()
                                 {
-                                    request_action = Some((
-                                        group_idx,
-                                        req_idx,
-                                        RequestAction::Select(request.clone()),
-                                    ));
+                                    request_action =
+                                        Some((group_idx, req_idx, RequestAction::Select));
                                 }
                                 if ui.button("❌").clicked() {
                                     request_action =
@@ -294,24 +816,83 @@ impl ApiTester {
             if let Some(group_idx) = export_group_idx {
                 if let Some(path) = FileDialog::new()
                     .add_filter("JSON", &["json"])
-                    .set_file_name(&format!("{}.json", self.groups[group_idx].name))
+                    .set_file_name(format!("{}.json", self.groups[group_idx].name))
                     .save_file()
                 {
-                    if let Err(e) = self.export_group(group_idx, path.to_str().unwrap()) {
+                    if let Err(e) = self.export_group(group_idx, &path) {
                         self.import_error = Some(e);
                     }
                 }
             }
+            if let Some(group_idx) = export_postman_idx {
+                if let Some(path) = FileDialog::new()
+                    .add_filter("JSON", &["json"])
+                    .set_file_name(format!(
+                        "{}.postman_collection.json",
+                        self.groups[group_idx].name
+                    ))
+                    .save_file()
+                {
+                    match self.export_group_postman(group_idx, &path) {
+                        Ok(warnings) => {
+                            self.import_error = None;
+                            self.import_warnings = warnings;
+                        }
+                        Err(e) => self.import_error = Some(e),
+                    }
+                }
+            }
+
+            if let Some(group_idx) = export_har_idx {
+                if let Some(path) = FileDialog::new()
+                    .add_filter("HAR", &["har"])
+                    .set_file_name(format!("{}.har", self.groups[group_idx].name))
+                    .save_file()
+                {
+                    match self.export_group_har(group_idx, &path) {
+                        Ok(warnings) => {
+                            self.import_error = None;
+                            self.import_warnings = warnings;
+                        }
+                        Err(e) => self.import_error = Some(e),
+                    }
+                }
+            }
 
             // 액션 처리
             match request_action {
                 Some((group_idx, _, RequestAction::Add)) => {
+                    self.websocket.close();
+                    self.grpc.cancel();
+                    self.stop_event_stream();
                     self.new_request_dialog.show = true;
                     self.new_request_dialog.group_index = Some(group_idx);
                     self.current_request = ApiRequest::default();
                 }
-                Some((group_idx, req_idx, RequestAction::Select(request))) => {
-                    self.current_request = request;
+                Some((group_idx, req_idx, RequestAction::Select)) => {
+                    self.websocket.close();
+                    self.grpc.cancel();
+                    self.stop_event_stream();
+                    self.current_request = self.groups[group_idx].requests[req_idx].clone();
+                    if self.current_request.params.is_empty() {
+                        sync_params_from_url(
+                            &self.current_request.url,
+                            &mut self.current_request.params,
+                        );
+                    }
+                    sync_path_params(
+                        &self.current_request.url,
+                        &mut self.current_request.path_params,
+                    );
+                    // 표가 없던 이전 버전의 x-www-form-urlencoded 바디는 표로 옮긴다
+                    let request = &mut self.current_request;
+                    if request.body_type == BodyType::FormUrlEncoded
+                        && !request.url_encoded_raw
+                        && request.url_encoded.is_empty()
+                        && !request.body.is_empty()
+                    {
+                        sync_key_values(decode_form(&request.body), &mut request.url_encoded);
+                    }
                     self.new_request_dialog.group_index = Some(group_idx);
                 }
                 Some((group_idx, req_idx, RequestAction::Delete)) => {
@@ -325,64 +906,165 @@ impl ApiTester {
 
             if let Some(idx) = group_to_delete {
                 self.groups.remove(idx);
+                self.group_dialog = GroupDialog::default();
                 self.save_groups();
             }
         });
     }
 
+    fn render_top_panel(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.heading("Ruquest");
+            ui.separator();
+
+            let mut active = self.environments.active.clone();
+            egui::ComboBox::from_label("Environment")
+                .selected_text(active.as_deref().unwrap_or("No Environment"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut active, None, "No Environment");
+                    for env in &self.environments.environments {
+                        ui.selectable_value(&mut active, Some(env.name.clone()), &env.name);
+                    }
+                });
+            if active != self.environments.active {
+                self.environments.active = active;
+                self.environments.save();
+            }
+
+            if ui.button("Manage Environments").clicked() {
+                self.environment_dialog.show = true;
+            }
+            ui.separator();
+            ui.toggle_value(&mut self.show_history, "History");
+        });
+    }
+
     fn render_main_panel(&mut self, ui: &mut Ui) {
+        let variables = self.active_variables();
+
         ui.horizontal(|ui| {
-            egui::ComboBox::from_label("Method")
-                .selected_text(&self.current_request.method)
+            egui::ComboBox::from_label("Type")
+                .selected_text(self.current_request.kind.label())
                 .show_ui(ui, |ui| {
-                    for method in &self.methods {
-                        ui.selectable_value(
-                            &mut self.current_request.method,
-                            method.clone(),
-                            method,
-                        );
+                    for kind in RequestKind::ALL {
+                        ui.selectable_value(&mut self.current_request.kind, kind, kind.label());
                     }
                 });
 
+            if self.current_request.kind == RequestKind::Http {
+                // 목록에 없는 PURGE, PROPFIND 같은 메소드는 직접 입력한다
+                let valid = parse_method(&self.current_request.method).is_ok();
+                let mut method_edit = egui::TextEdit::singleline(&mut self.current_request.method)
+                    .hint_text("METHOD")
+                    .desired_width(80.0);
+                if !valid {
+                    method_edit = method_edit.text_color(Color32::RED);
+                }
+                let mut method_changed = ui.add(method_edit).changed();
+                egui::ComboBox::from_id_salt("method_presets")
+                    .selected_text("")
+                    .width(0.0)
+                    .show_ui(ui, |ui| {
+                        for method in &self.methods {
+                            method_changed |= ui
+                                .selectable_value(
+                                    &mut self.current_request.method,
+                                    method.clone(),
+                                    method,
+                                )
+                                .changed();
+                        }
+                    });
+                if method_changed {
+                    self.save_current_request();
+                }
+            }
+
             ui.label("URL:");
-            let url_changed = ui
-                .text_edit_singleline(&mut self.current_request.url)
-                .changed();
+            let url_changed =
+                variable_text_edit(ui, &mut self.current_request.url, &variables, false).changed();
 
             // Command+S나 Ctrl+S로 저장
             if ui.input(|i| i.modifiers.command && i.key_pressed(egui::Key::S)) {
-                if let Some(group_idx) = self.new_request_dialog.group_index {
-                    if group_idx < self.groups.len() {
-                        // 현재 요청 업데이트
-                        for request in &mut self.groups[group_idx].requests {
-                            if request.name == self.current_request.name {
-                                *request = self.current_request.clone();
-                                self.save_groups();
-                                break;
-                            }
-                        }
-                    }
-                }
+                self.save_current_request();
             }
 
             // URL이 변경되었을 때도 저장
             if url_changed {
-                if let Some(group_idx) = self.new_request_dialog.group_index {
-                    if group_idx < self.groups.len() {
-                        // 현재 요청 업데이트
-                        for request in &mut self.groups[group_idx].requests {
-                            if request.name == self.current_request.name {
-                                *request = self.current_request.clone();
-                                self.save_groups();
-                                break;
-                            }
-                        }
+                sync_params_from_url(&self.current_request.url, &mut self.current_request.params);
+                sync_path_params(
+                    &self.current_request.url,
+                    &mut self.current_request.path_params,
+                );
+                self.save_current_request();
+            }
+
+            if self.current_request.kind == RequestKind::WebSocket {
+                if self.websocket.commands.is_some() {
+                    if ui.button("Disconnect").clicked() {
+                        self.websocket.close();
+                    }
+                } else if ui.button("Connect").clicked() {
+                    self.connect_websocket(ui.ctx());
+                }
+            } else if self.current_request.kind == RequestKind::Grpc {
+                if self.grpc.cancel.is_some() {
+                    if ui.button("Cancel").clicked() {
+                        self.grpc.cancel();
                     }
+                } else if ui.button("Invoke").clicked() {
+                    self.invoke_grpc(ui.ctx());
+                }
+            } else {
+                if ui.button("Send").clicked() && !self.is_loading {
+                    self.send_request();
+                }
+                if ui.button("Copy as cURL").clicked() {
+                    self.copy_as_curl(ui.ctx());
                 }
             }
+        });
+        if let Some(status) = &self.curl_status {
+            ui.label(status);
+        }
+
+        if let (true, Some((sent, total))) = (self.is_loading, self.upload_progress) {
+            let progress = if total == 0 {
+                1.0
+            } else {
+                sent as f32 / total as f32
+            };
+            ui.add(
+                egui::ProgressBar::new(progress)
+                    .text(format!("Uploading {} / {} bytes", sent, total)),
+            );
+        }
+
+        ui.collapsing("Params", |ui| {
+            if render_key_value_table(ui, &mut self.current_request.params, "Add Param") {
+                self.current_request.url =
+                    url_with_params(&self.current_request.url, &self.current_request.params);
+                self.save_current_request();
+            }
+        });
+
+        if !self.current_request.path_params.is_empty() {
+            ui.collapsing("Path Params", |ui| {
+                if render_path_params(ui, &mut self.current_request.path_params, &variables) {
+                    self.save_current_request();
+                }
+            });
+        }
 
-            if ui.button("Send").clicked() && !self.is_loading {
-                self.send_request();
+        ui.collapsing("Authorization", |ui| {
+            let response = render_auth(ui, &mut self.current_request.auth, true);
+            if response.get_new_token {
+                if let Some(target) = self.oauth_target() {
+                    self.request_oauth_token(ui.ctx(), target);
+                }
+            }
+            if let Some(status) = &self.oauth_status {
+                ui.label(status);
             }
         });
 
@@ -390,96 +1072,814 @@ impl ApiTester {
             self.render_headers(ui);
         });
 
-        if self.current_request.method != "GET" {
-            ui.collapsing("Body", |ui| {
-                ui.text_edit_multiline(&mut self.current_request.body);
-            });
-        }
+        ui.collapsing("Variables", |ui| {
+            if render_key_values(ui, &mut self.current_request.variables, "Add Variable") {
+                self.save_current_request();
+            }
+        });
 
-        if let Some(response) = &self.current_request.response {
-            self.render_response(ui, response);
+        ui.collapsing("Settings", |ui| {
+            // GraphQL과 JSON-RPC 응답은 이벤트 스트림으로 다루지 않는다
+            if self.current_request.kind == RequestKind::Http
+                && ui
+                    .checkbox(
+                        &mut self.current_request.sse_reconnect,
+                        "Reconnect event streams with Last-Event-ID",
+                    )
+                    .changed()
+            {
+                self.save_current_request();
+            }
+            if ui
+                .checkbox(
+                    &mut self.current_request.insecure,
+                    "Skip TLS certificate verification",
+                )
+                .changed()
+            {
+                self.save_current_request();
+            }
+        });
+
+        if !self.current_request.examples.is_empty() {
+            let mut show = None;
+            let mut delete = None;
+            ui.collapsing(
+                format!("Examples ({})", self.current_request.examples.len()),
+                |ui| {
+                    for (idx, example) in self.current_request.examples.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui.button(&example.name).clicked() {
+                                show = Some(idx);
+                            }
+                            if ui.button("❌").clicked() {
+                                delete = Some(idx);
+                            }
+                        });
+                    }
+                },
+            );
+            // 예시를 누르면 응답 영역에 보여준다
+            if let Some(idx) = show {
+                self.current_request.response = Some(self.current_request.examples[idx].response());
+            }
+            if let Some(idx) = delete {
+                self.current_request.examples.remove(idx);
+                self.save_current_request();
+            }
         }
-    }
-    fn send_request(&mut self) {
-        let req = self.current_request.clone();
-        let tx = self.tx.clone();
-        self.is_loading = true;
 
-        self.runtime.spawn(async move {
-            let client = Client::new();
-            let method = match req.method.as_str() {
-                "GET" => Method::GET,
-                "POST" => Method::POST,
-                "PUT" => Method::PUT,
-                "DELETE" => Method::DELETE,
-                "PATCH" => Method::PATCH,
-                _ => {
-                    let _ = tx.send(ApiResponse {
-                        status: 0,
-                        headers: HeaderMap::new(),
-                        body: format!("Error: Invalid HTTP method '{}'", req.method),
-                        time_taken: Duration::from_secs(0),
-                    });
-                    return;
+        if self.current_request.kind == RequestKind::WebSocket {
+            ui.collapsing("WebSocket", |ui| {
+                if render_websocket(ui, &mut self.current_request.websocket, &mut self.websocket) {
+                    self.save_current_request();
                 }
-            };
+            });
+            return;
+        }
 
-            let start_time = std::time::Instant::now();
-            let mut request = client.request(method, &req.url);
+        if self.current_request.kind == RequestKind::Grpc {
+            ui.collapsing("gRPC", |ui| {
+                let response = render_grpc(ui, &mut self.current_request.grpc, &self.grpc);
+                if response.changed {
+                    self.save_current_request();
+                }
+                if response.load {
+                    self.load_grpc_descriptors(ui.ctx());
+                }
+                if let Some(status) = &self.grpc_status {
+                    ui.label(status);
+                }
+            });
+            render_grpc_response(ui, &self.grpc);
+            return;
+        }
 
-            let mut headers = HeaderMap::new();
-            headers.insert(
-                HeaderName::from_static("content-type"),
-                "application/json".parse().unwrap(),
-            );
+        if self.current_request.kind == RequestKind::GraphQl {
+            ui.collapsing("GraphQL", |ui| {
+                let response = render_graphql(ui, &mut self.current_request.graphql, &variables);
+                if response.changed {
+                    self.save_current_request();
+                }
+                if response.fetch_schema {
+                    self.fetch_graphql_schema();
+                }
+                if let Some(status) = &self.graphql_status {
+                    ui.label(status);
+                }
+            });
+        } else if self.current_request.kind == RequestKind::JsonRpc {
+            ui.collapsing("JSON-RPC", |ui| {
+                if render_jsonrpc(ui, &mut self.current_request.jsonrpc) {
+                    self.save_current_request();
+                }
+            });
+        } else if !BODYLESS_METHODS.contains(&self.current_request.method.as_str()) {
+            ui.collapsing("Body", |ui| {
+                ui.horizontal(|ui| {
+                    for body_type in BodyType::ALL {
+                        ui.selectable_value(
+                            &mut self.current_request.body_type,
+                            body_type,
+                            body_type.label(),
+                        );
+                    }
+                });
 
-            for (key, value) in req.headers {
-                if !key.is_empty() && !value.is_empty() {
-                    if let Ok(header_name) = HeaderName::from_bytes(key.as_bytes()) {
-                        if let Ok(header_value) = value.parse() {
-                            headers.insert(header_name, header_value);
+                match self.current_request.body_type {
+                    BodyType::None => {
+                        ui.label("This request does not have a body");
+                    }
+                    BodyType::FormData => {
+                        if render_form_data(ui, &mut self.current_request.form_data, &variables) {
+                            self.save_current_request();
                         }
                     }
-                }
-            }
-            request = request.headers(headers);
-
-            if !req.body.is_empty() {
-                match serde_json::from_str::<Value>(&req.body) {
-                    Ok(json) => {
-                        request = request.json(&json);
+                    BodyType::Binary => {
+                        if render_binary_body(ui, &mut self.current_request.binary_path, &variables)
+                        {
+                            self.save_current_request();
+                        }
                     }
-                    Err(_) => {
-                        request = request.body(req.body);
+                    BodyType::FormUrlEncoded => {
+                        if render_url_encoded(ui, &mut self.current_request, &variables) {
+                            self.save_current_request();
+                        }
+                    }
+                    _ => {
+                        variable_text_edit(ui, &mut self.current_request.body, &variables, true);
                     }
                 }
-            }
-
-            match request.send().await {
+            });
+        }
+
+        if self.event_stream.is_some() || self.event_stream_status.is_some() {
+            ui.separator();
+            ui.horizontal(|ui| {
+                if self.event_stream.is_some() && ui.button("Stop").clicked() {
+                    self.stop_event_stream();
+                    self.event_stream_status = Some("Stopped".to_string());
+                }
+                if let Some(status) = &self.event_stream_status {
+                    ui.label(status);
+                }
+            });
+        }
+
+        if let Some(response) = &self.current_request.response {
+            self.render_response(ui, response);
+        }
+    }
+
+    fn record_history(&mut self, response: &ApiResponse) {
+        if let Some((group, request, sent)) = self.history_pending.take() {
+            self.history
+                .push(HistoryEntry::new(group, &request, &sent, response));
+        }
+    }
+
+    // 기록된 요청과 응답을 편집기로 되돌린다. 원래 그룹이 남아 있으면 그 요청으로 연결한다
+    fn restore_history(&mut self, idx: usize) {
+        let Some(entry) = self.history.entries.get(idx).cloned() else {
+            return;
+        };
+        self.websocket.close();
+        self.grpc.cancel();
+        self.stop_event_stream();
+        self.new_request_dialog.group_index = entry.group.as_ref().and_then(|name| {
+            self.groups.iter().position(|group| {
+                &group.name == name
+                    && group
+                        .requests
+                        .iter()
+                        .any(|request| request.name == entry.request.name)
+            })
+        });
+        self.current_request = entry.request.clone();
+        self.current_request.response = Some(entry.response());
+        // 기록에는 예시 응답이 없으므로 그룹에 저장된 요청의 것을 유지한다
+        if let Some(request) = self.current_group().and_then(|group| {
+            group
+                .requests
+                .iter()
+                .find(|request| request.name == entry.request.name)
+        }) {
+            self.current_request.examples = request.examples.clone();
+        }
+    }
+
+    // 기록된 요청을 그룹에 새 요청으로 추가한다. 이름이 겹치면 번호를 붙인다
+    fn save_history_to_group(&mut self, idx: usize, group_idx: usize) {
+        let (Some(entry), Some(group)) = (
+            self.history.entries.get(idx),
+            self.groups.get_mut(group_idx),
+        ) else {
+            return;
+        };
+        let mut request = entry.request.clone();
+        if request.name.is_empty() {
+            request.name = format!("{} {}", entry.method, entry.url);
+        }
+        request.name = import::unique_request_name(&group.requests, &request.name);
+        group.requests.push(request);
+        self.save_groups();
+    }
+
+    fn render_history_panel(&mut self, ui: &mut Ui) {
+        ui.heading("History");
+
+        if let Some(error) = &self.history_error {
+            ui.label(RichText::new(error).color(Color32::RED));
+            ui.horizontal(|ui| {
+                if ui.button("Retry").clicked() {
+                    match History::load() {
+                        Ok(loaded) => {
+                            self.history.merge_loaded(loaded);
+                            self.history_error = None;
+                        }
+                        Err(e) => self.history_error = Some(e),
+                    }
+                }
+                if ui.button("Back up and start empty").clicked() {
+                    match back_up_file(history::HISTORY_FILE) {
+                        Ok(_) => {
+                            self.history.prune();
+                            self.history_error = None;
+                        }
+                        Err(e) => self.history_error = Some(e),
+                    }
+                }
+            });
+        }
+
+        let filter = &mut self.history_filter;
+        ui.add(egui::TextEdit::singleline(&mut filter.search).hint_text("Search URL, name, body"));
+        ui.horizontal(|ui| {
+            let mut methods: Vec<&str> = self
+                .history
+                .entries
+                .iter()
+                .map(|entry| entry.method.as_str())
+                .collect();
+            methods.sort();
+            methods.dedup();
+            egui::ComboBox::from_id_salt("history_method")
+                .selected_text(filter.method.as_deref().unwrap_or("Any method"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut filter.method, None, "Any method");
+                    for method in methods {
+                        ui.selectable_value(&mut filter.method, Some(method.to_string()), method);
+                    }
+                });
+
+            egui::ComboBox::from_id_salt("history_status")
+                .selected_text(filter.status.label())
+                .show_ui(ui, |ui| {
+                    for status in StatusFilter::ALL {
+                        ui.selectable_value(&mut filter.status, status, status.label());
+                    }
+                });
+
+            let mut hosts: Vec<String> = self.history.entries.iter().map(|e| e.host()).collect();
+            hosts.sort();
+            hosts.dedup();
+            egui::ComboBox::from_id_salt("history_host")
+                .selected_text(filter.host.as_deref().unwrap_or("Any host"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut filter.host, None, "Any host");
+                    for host in hosts {
+                        ui.selectable_value(&mut filter.host, Some(host.clone()), host);
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Keep last");
+            // 드래그 중에는 지우지 않고 놓았을 때 한 번만 정리한다
+            let limit = ui.add(egui::DragValue::new(&mut self.history.limit).range(1..=10000));
+            if limit.drag_stopped() || (limit.changed() && !limit.dragged()) {
+                self.history.prune();
+            }
+            if ui.button("Clear").clicked() {
+                self.history.clear();
+            }
+            if ui.button("Export HAR").clicked() {
+                if let Some(path) = FileDialog::new()
+                    .add_filter("HAR", &["har"])
+                    .set_file_name("history.har")
+                    .save_file()
+                {
+                    if let Err(e) = self.export_history_har(&path) {
+                        self.import_error = Some(e);
+                    }
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Save to:");
+            egui::ComboBox::from_id_salt("history_group")
+                .selected_text(
+                    self.history_target_group
+                        .and_then(|idx| self.groups.get(idx))
+                        .map(|group| group.name.as_str())
+                        .unwrap_or("Select group"),
+                )
+                .show_ui(ui, |ui| {
+                    for (idx, group) in self.groups.iter().enumerate() {
+                        ui.selectable_value(&mut self.history_target_group, Some(idx), &group.name);
+                    }
+                });
+        });
+        ui.separator();
+
+        let mut restore = None;
+        let mut save = None;
+        let mut delete = None;
+        ScrollArea::vertical()
+            .id_salt("history_entries")
+            .show(ui, |ui| {
+                for (idx, entry) in self.history.entries.iter().enumerate() {
+                    if !self.history_filter.matches(entry) {
+                        continue;
+                    }
+                    let status_color = match entry.status {
+                        0 => Color32::GRAY,
+                        status if status < 300 => Color32::GREEN,
+                        status if status < 400 => Color32::YELLOW,
+                        _ => Color32::RED,
+                    };
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(entry.display_time()).weak());
+                        ui.label(RichText::new(&entry.method).strong());
+                        let status = match entry.status {
+                            0 => "ERR".to_string(),
+                            status => status.to_string(),
+                        };
+                        ui.label(RichText::new(status).color(status_color));
+                        ui.label(RichText::new(format!("{}ms", entry.time_ms)).weak());
+                    });
+                    ui.label(&entry.url);
+                    ui.horizontal(|ui| {
+                        if ui.button("Restore").clicked() {
+                            restore = Some(idx);
+                        }
+                        if ui
+                            .add_enabled(
+                                self.history_target_group.is_some(),
+                                egui::Button::new("Save to Group"),
+                            )
+                            .clicked()
+                        {
+                            save = Some(idx);
+                        }
+                        if ui.button("🗑").clicked() {
+                            delete = Some(idx);
+                        }
+                    });
+                    ui.separator();
+                }
+            });
+
+        if let Some(idx) = restore {
+            self.restore_history(idx);
+        }
+        if let (Some(idx), Some(group_idx)) = (save, self.history_target_group) {
+            self.save_history_to_group(idx, group_idx);
+        }
+        if let Some(idx) = delete {
+            self.history.remove(idx);
+        }
+    }
+
+    fn stop_event_stream(&mut self) {
+        if let Some(cancel) = self.event_stream.take() {
+            cancel.cancel();
+        }
+        self.event_stream_status = None;
+    }
+    fn send_request(&mut self) {
+        let mut req = match self.resolve_request() {
+            Ok(req) => req,
+            Err(e) => {
+                self.current_request.response = Some(ApiResponse::error(e));
+                return;
+            }
+        };
+        req.auth.apply(&mut req.headers, &mut req.url);
+        if req.kind == RequestKind::JsonRpc {
+            // 사용한 id를 기억해 다음 요청은 새 id로 보내고 응답을 맞춰본다
+            self.current_request.jsonrpc.next_id = req.jsonrpc.next_id;
+            self.current_request.jsonrpc.sent = req.jsonrpc.sent.clone();
+            self.save_current_request();
+        }
+        self.stop_event_stream();
+        self.history_pending = Some((
+            self.current_group().map(|group| group.name.clone()),
+            self.current_request.clone(),
+            req.clone(),
+        ));
+        let token_target = self.oauth_target();
+        let tx = self.tx.clone();
+        self.is_loading = true;
+
+        self.runtime.spawn(async move {
+            let client = http_client(req.insecure);
+
+            // OAuth2 토큰이 없거나 만료가 가까우면 먼저 발급/갱신
+            if let (Auth::OAuth2(config), Some(target)) = (&req.auth, token_target) {
+                match oauth2::ensure_token(&client, config).await {
+                    Ok(Some(token)) => {
+                        set_header(
+                            &mut req.headers,
+                            "Authorization",
+                            token.authorization_value(),
+                        );
+                        let _ = tx.send(ApiEvent::OAuthToken(target, token));
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        let _ = tx.send(ApiEvent::Response(ApiResponse::error(e)));
+                        return;
+                    }
+                }
+            }
+
+            let method = match parse_method(&req.method) {
+                Ok(method) => method,
+                Err(e) => {
+                    let _ = tx.send(ApiEvent::Response(ApiResponse::error(e)));
+                    return;
+                }
+            };
+
+            let start_time = std::time::Instant::now();
+
+            let mut headers = header_map(&req.headers);
+
+            // 바디는 입력한 그대로 보내고, Content-Type은 사용자가 지정하지 않았을 때만 채운다
+            let mut request_body = match req.body_type {
+                BodyType::FormData => RequestBody::FormData(req.form_data),
+                BodyType::Binary => RequestBody::File(req.binary_path),
+                _ => RequestBody::Bytes(req.body.into_bytes()),
+            };
+            set_content_type(&mut headers, req.body_type, !request_body.is_empty());
+
+            // 서명과 Digest 계산에만 바디 전체를 메모리에 올린다.
+            // multipart는 서명한 바이트를 그대로 보내도록 여기서 미리 직렬화한다
+            let body = match &req.auth {
+                Auth::AwsSigV4(_) | Auth::Hmac(_) | Auth::Digest { .. } => {
+                    let contents =
+                        match &request_body {
+                            RequestBody::FormData(parts) => body::encode_multipart(parts)
+                                .await
+                                .map(|(bytes, content_type)| {
+                                    if let Ok(value) = HeaderValue::from_str(&content_type) {
+                                        headers.insert(CONTENT_TYPE, value);
+                                    }
+                                    request_body = RequestBody::Bytes(bytes.clone());
+                                    bytes
+                                }),
+                            _ => request_body.contents().await,
+                        };
+                    match contents {
+                        Ok(body) => body,
+                        Err(e) => {
+                            let _ = tx.send(ApiEvent::Response(ApiResponse::error(e)));
+                            return;
+                        }
+                    }
+                }
+                _ => Vec::new(),
+            };
+
+            let reconnect = req.sse_reconnect && req.kind == RequestKind::Http;
+            let prepared = PreparedRequest {
+                client,
+                method: method.clone(),
+                url: req.url,
+                headers,
+                auth: req.auth,
+                body: request_body,
+                contents: body,
+            };
+            let result = prepared.send(HeaderMap::new(), &tx).await;
+
+            match result {
+                // HEAD 응답은 바디가 없으므로 스트림으로 읽지 않는다
+                Ok(response)
+                    if method != Method::HEAD && sse::is_event_stream(response.headers()) =>
+                {
+                    stream_events(response, &prepared, reconnect, &tx, start_time).await;
+                }
                 Ok(response) => {
                     let status = response.status().as_u16();
                     let headers = response.headers().clone();
                     let body = response.text().await.unwrap_or_default();
                     let time_taken = start_time.elapsed();
 
-                    let _ = tx.send(ApiResponse {
+                    let _ = tx.send(ApiEvent::Response(ApiResponse {
+                        method: method.to_string(),
                         status,
                         headers,
                         body,
                         time_taken,
-                    });
+                        events: Vec::new(),
+                    }));
                 }
                 Err(e) => {
-                    let _ = tx.send(ApiResponse {
-                        status: 0,
-                        headers: HeaderMap::new(),
-                        body: format!("Error: {}", e),
+                    let _ = tx.send(ApiEvent::Response(ApiResponse {
                         time_taken: start_time.elapsed(),
-                    });
+                        ..ApiResponse::error(e)
+                    }));
+                }
+            }
+        });
+    }
+    // 변수와 인증을 적용한 핸드셰이크로 WebSocket 연결을 연다
+    fn connect_websocket(&mut self, ctx: &Context) {
+        let mut req = match self.resolve_request() {
+            Ok(req) => req,
+            Err(e) => {
+                self.websocket.status = Some(e);
+                return;
+            }
+        };
+        req.auth.apply(&mut req.headers, &mut req.url);
+        let headers: Vec<(String, String)> = req
+            .headers
+            .into_iter()
+            .filter(|header| !header.key.is_empty() && !header.value.is_empty())
+            .map(|header| (header.key, header.value))
+            .collect();
+
+        let (session_id, commands) = self.websocket.start();
+        let subprotocols = req.websocket.subprotocols;
+        let tx = self.tx.clone();
+        let ctx = ctx.clone();
+        self.runtime.spawn(websocket::run(
+            req.url,
+            headers,
+            subprotocols,
+            req.insecure,
+            commands,
+            move |event| {
+                let _ = tx.send(ApiEvent::WebSocket(session_id, event));
+                ctx.request_repaint();
+            },
+        ));
+    }
+
+    // .proto 파일이나 서버 리플렉션에서 서비스 정의를 불러온다
+    fn load_grpc_descriptors(&mut self, ctx: &Context) {
+        let mut req = match self.resolve_request() {
+            Ok(req) => req,
+            Err(e) => {
+                self.grpc_status = Some(e);
+                return;
+            }
+        };
+        req.auth.apply(&mut req.headers, &mut req.url);
+        self.grpc_status = Some("Loading services...".to_string());
+
+        let name = self.current_request.name.clone();
+        let tx = self.tx.clone();
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let result = if req.grpc.use_reflection {
+                match grpc::connect(&req.url).await {
+                    Ok(channel) => grpc::load_reflection(channel, &header_map(&req.headers)).await,
+                    Err(e) => Err(e),
+                }
+            } else {
+                let files = req.grpc.proto_files;
+                tokio::task::spawn_blocking(move || grpc::load_files(&files))
+                    .await
+                    .unwrap_or_else(|e| Err(e.to_string()))
+            };
+            let _ = tx.send(ApiEvent::GrpcDescriptors { name, result });
+            ctx.request_repaint();
+        });
+    }
+
+    // 선택한 메소드를 호출한다. 요청 헤더는 메타데이터로 보낸다
+    fn invoke_grpc(&mut self, ctx: &Context) {
+        let mut req = match self.resolve_request() {
+            Ok(req) => req,
+            Err(e) => {
+                self.grpc.status = Some(e);
+                return;
+            }
+        };
+        let Some(method) = self.current_request.grpc.method_descriptor() else {
+            self.grpc.status = Some("Load services and select a method first".to_string());
+            return;
+        };
+        req.auth.apply(&mut req.headers, &mut req.url);
+
+        let (call_id, cancel) = self.grpc.start();
+        let metadata = header_map(&req.headers);
+        let tx = self.tx.clone();
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let emit = |event| {
+                let _ = tx.send(ApiEvent::Grpc(call_id, event));
+                ctx.request_repaint();
+            };
+            let call = async {
+                match grpc::connect(&req.url).await {
+                    Ok(channel) => {
+                        grpc::call(channel, method, metadata, req.grpc.message, &emit).await
+                    }
+                    Err(e) => emit(GrpcEvent::Finished {
+                        code: tonic::Code::Unavailable,
+                        message: e,
+                        trailers: Vec::new(),
+                    }),
+                }
+            };
+            tokio::select! {
+                _ = cancel.cancelled() => {}
+                _ = call => {}
+            }
+        });
+    }
+
+    // 변수와 인증을 적용한 현재 요청을 curl 명령으로 클립보드에 복사한다
+    fn copy_as_curl(&mut self, ctx: &egui::Context) {
+        let mut req = match self.resolve_request() {
+            Ok(req) => req,
+            Err(e) => {
+                self.curl_status = Some(format!("Error: {}", e));
+                return;
+            }
+        };
+        req.auth.apply(&mut req.headers, &mut req.url);
+
+        // HMAC 서명은 시각이 들어가므로 복사하는 시점에 계산한다
+        if let Auth::Hmac(config) = &req.auth {
+            if matches!(req.body_type, BodyType::FormData | BodyType::Binary) {
+                self.curl_status =
+                    Some("Error: HMAC signatures can't be copied for file bodies".to_string());
+                return;
+            }
+            let signed = parse_method(&req.method)
+                .and_then(|method| {
+                    reqwest::Url::parse(&req.url)
+                        .map_err(|e| e.to_string())
+                        .map(|url| (method, url))
+                })
+                .and_then(|(method, url)| {
+                    hmac_signature::sign(
+                        config,
+                        &method,
+                        &url,
+                        req.body.as_bytes(),
+                        oauth2::now_secs(),
+                    )
+                });
+            match signed {
+                Ok(signed_headers) => {
+                    for (key, value) in signed_headers {
+                        set_header(&mut req.headers, &key, value);
+                    }
+                }
+                Err(e) => {
+                    self.curl_status = Some(format!("Error: Failed to sign request: {}", e));
+                    return;
+                }
+            }
+        }
+
+        ctx.copy_text(curl::to_command(&req));
+        self.curl_status = Some("Copied cURL command to clipboard".to_string());
+    }
+
+    // 현재 요청의 엔드포인트에 introspection 쿼리를 보내 스키마를 가져온다
+    fn fetch_graphql_schema(&mut self) {
+        let mut req = match self.resolve_request() {
+            Ok(req) => req,
+            Err(e) => {
+                self.graphql_status = Some(e);
+                return;
+            }
+        };
+        req.auth.apply(&mut req.headers, &mut req.url);
+        self.graphql_status = Some("Fetching schema...".to_string());
+
+        let url = self.current_request.url.clone();
+        let tx = self.tx.clone();
+        self.runtime.spawn(async move {
+            let client = http_client(req.insecure);
+            let result = graphql::introspect(&client, &req.url, header_map(&req.headers)).await;
+            let _ = tx.send(ApiEvent::GraphQlSchema { url, result });
+        });
+    }
+
+    // 현재 요청에 적용될 OAuth2 설정이 저장된 위치
+    fn oauth_target(&self) -> Option<TokenTarget> {
+        match &self.current_request.auth {
+            Auth::OAuth2(_) => Some(TokenTarget::Request {
+                group_index: self.new_request_dialog.group_index,
+                name: self.current_request.name.clone(),
+            }),
+            Auth::Inherit => {
+                let group_idx = self.new_request_dialog.group_index?;
+                match self.groups.get(group_idx)?.auth {
+                    Auth::OAuth2(_) => Some(TokenTarget::Group(group_idx)),
+                    _ => None,
                 }
             }
+            _ => None,
+        }
+    }
+
+    fn request_oauth_token(&mut self, ctx: &Context, target: TokenTarget) {
+        let auth = match &target {
+            TokenTarget::Request { .. } => self.current_request.auth.clone(),
+            TokenTarget::Group(idx) => match self.groups.get(*idx) {
+                Some(group) => group.auth.clone(),
+                None => return,
+            },
+        };
+
+        let variables = self.active_variables();
+        let mut resolver = Resolver::new(&variables);
+        let auth = auth.resolve(&mut resolver);
+        if let Err(e) = resolver.finish() {
+            self.oauth_status = Some(e);
+            return;
+        }
+        let Auth::OAuth2(config) = auth else {
+            return;
+        };
+
+        let pending = if config.grant_type == oauth2::GrantType::AuthorizationCode {
+            match oauth2::begin_authorization(&config) {
+                Ok(pending) => {
+                    ctx.open_url(egui::OpenUrl::new_tab(&pending.authorize_url));
+                    self.oauth_status = Some(format!(
+                        "Waiting for authorization in the browser: {}",
+                        pending.authorize_url
+                    ));
+                    Some(pending)
+                }
+                Err(e) => {
+                    self.oauth_status = Some(e);
+                    return;
+                }
+            }
+        } else {
+            self.oauth_status = Some("Requesting token...".to_string());
+            None
+        };
+
+        let insecure = self.current_request.insecure;
+        let tx = self.tx.clone();
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let client = http_client(insecure);
+            let result = match pending {
+                Some(pending) => oauth2::complete_authorization(&client, &config, pending).await,
+                None => oauth2::fetch_token(&client, &config).await,
+            };
+            let _ = match result {
+                Ok(token) => tx.send(ApiEvent::OAuthToken(target, token)),
+                Err(e) => tx.send(ApiEvent::OAuthError(e)),
+            };
+            ctx.request_repaint();
         });
     }
+
+    fn store_oauth_token(&mut self, target: TokenTarget, token: OAuth2Token) {
+        let set_token = |auth: &mut Auth| {
+            if let Auth::OAuth2(config) = auth {
+                config.token = Some(token.clone());
+            }
+        };
+
+        match target {
+            TokenTarget::Request { group_index, name } => {
+                if group_index == self.new_request_dialog.group_index
+                    && name == self.current_request.name
+                {
+                    set_token(&mut self.current_request.auth);
+                }
+                if let Some(group) = group_index.and_then(|idx| self.groups.get_mut(idx)) {
+                    if let Some(request) = group.requests.iter_mut().find(|r| r.name == name) {
+                        set_token(&mut request.auth);
+                    }
+                }
+            }
+            TokenTarget::Group(idx) => {
+                if let Some(group) = self.groups.get_mut(idx) {
+                    set_token(&mut group.auth);
+                }
+            }
+        }
+        self.save_groups();
+    }
+
     fn render_dialogs(&mut self, ctx: &Context) {
         // 새 그룹 생성 다이얼로그
         if self.new_group_dialog.show {
@@ -494,8 +1894,8 @@ impl ApiTester {
                         if ui.button("Create").clicked() && !self.new_group_dialog.name.is_empty() {
                             self.groups.push(RequestGroup {
                                 name: self.new_group_dialog.name.clone(),
-                                requests: Vec::new(),
                                 is_expanded: true,
+                                ..Default::default()
                             });
                             self.save_groups();
                             self.new_group_dialog.name.clear();
@@ -509,6 +1909,90 @@ impl ApiTester {
                 });
         }
 
+        // HAR 가져오기 다이얼로그
+        if self.har_dialog.show {
+            let mut pick = false;
+            egui::Window::new("Import HAR")
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    ui.checkbox(
+                        &mut self.har_dialog.attach_responses,
+                        "Attach recorded responses as examples",
+                    );
+                    ui.horizontal(|ui| {
+                        pick = ui.button("Choose File...").clicked();
+                        if ui.button("Cancel").clicked() {
+                            self.har_dialog.show = false;
+                        }
+                    });
+                });
+
+            if pick {
+                if let Some(path) = FileDialog::new()
+                    .add_filter("HAR", &["har", "json"])
+                    .pick_file()
+                {
+                    match self.import_har(&path, self.har_dialog.attach_responses) {
+                        Ok(warnings) => {
+                            self.import_error = None;
+                            self.import_warnings = warnings;
+                        }
+                        Err(e) => {
+                            self.import_error = Some(e);
+                            self.import_warnings.clear();
+                        }
+                    }
+                    self.har_dialog.show = false;
+                }
+            }
+        }
+
+        // cURL 명령 가져오기 다이얼로그
+        if self.curl_dialog.show {
+            let mut import = false;
+            let mut close = false;
+            egui::Window::new("Import cURL")
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    ui.label("Paste a curl command:");
+                    ui.add(
+                        egui::TextEdit::multiline(&mut self.curl_dialog.command)
+                            .code_editor()
+                            .desired_rows(8)
+                            .desired_width(500.0),
+                    );
+                    if let Some(error) = &self.curl_dialog.error {
+                        ui.colored_label(Color32::RED, error);
+                    }
+                    ui.horizontal(|ui| {
+                        import = ui.button("Import").clicked();
+                        close = ui.button("Cancel").clicked();
+                    });
+                });
+
+            if import {
+                match curl::parse(&self.curl_dialog.command) {
+                    // 가져온 요청은 새 요청 다이얼로그에서 이름을 정해 그룹에 추가한다
+                    Ok((request, warnings)) => {
+                        self.websocket.close();
+                        self.grpc.cancel();
+                        self.stop_event_stream();
+                        self.new_request_dialog.name = request.name.clone();
+                        self.new_request_dialog.group_index = self.curl_dialog.group_index;
+                        self.new_request_dialog.show = true;
+                        self.current_request = request;
+                        self.import_error = None;
+                        self.import_warnings = warnings;
+                        close = true;
+                    }
+                    Err(e) => self.curl_dialog.error = Some(e),
+                }
+            }
+            if close {
+                self.curl_dialog = CurlDialog::default();
+            }
+        }
+
         // 새 API 요청 생성 다이얼로그
         if self.new_request_dialog.show {
             egui::Window::new("New API Request")
@@ -539,36 +2023,1512 @@ impl ApiTester {
                     });
                 });
         }
-    }
-}
 
-impl eframe::App for ApiTester {
-    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
-        if let Ok(response) = self.rx.try_recv() {
-            self.current_request.response = Some(response);
-            self.is_loading = false;
-        }
+        // 그룹 변수/기본 헤더 편집 다이얼로그
+        if self.group_dialog.show {
+            let variables = self.active_variables();
+            let group_idx = self.group_dialog.group_index;
+            if let Some(group) = group_idx.and_then(|idx| self.groups.get_mut(idx)) {
+                let mut changed = false;
+                let mut close = false;
+                let mut get_new_token = false;
+                let oauth_status = &self.oauth_status;
 
-        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            ui.horizontal(|ui| {
-                ui.heading("Ruquest");
-            });
-        });
+                egui::Window::new(format!("Group: {}", group.name))
+                    .collapsible(false)
+                    .show(ctx, |ui| {
+                        ui.label("Variables");
+                        changed |= render_key_values(ui, &mut group.variables, "Add Variable");
+                        ui.separator();
+                        ui.label("Default Headers");
+                        changed |= render_header_table(ui, &mut group.headers, &variables);
+                        ui.separator();
+                        ui.label("Default Authorization");
+                        let response = render_auth(ui, &mut group.auth, false);
+                        changed |= response.changed;
+                        get_new_token = response.get_new_token;
+                        if let Some(status) = oauth_status {
+                            ui.label(status);
+                        }
+                        ui.separator();
+                        if ui.button("Close").clicked() {
+                            close = true;
+                        }
+                    });
 
-        egui::SidePanel::left("requests_panel")
-            .resizable(true)
-            .default_width(400.0)
-            .min_width(300.0)
-            .max_width(500.0) // 최대 크기 설정
+                if changed {
+                    self.save_groups();
+                }
+                if close {
+                    self.group_dialog.show = false;
+                    self.group_dialog.group_index = None;
+                }
+                if get_new_token {
+                    if let Some(idx) = group_idx {
+                        self.request_oauth_token(ctx, TokenTarget::Group(idx));
+                    }
+                }
+            } else {
+                self.group_dialog.show = false;
+            }
+        }
+
+        // 환경 관리 다이얼로그
+        if self.environment_dialog.show {
+            egui::Window::new("Environments")
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    let mut changed = false;
+                    let dialog = &mut self.environment_dialog;
+                    let store = &mut self.environments;
+
+                    ui.horizontal(|ui| {
+                        ui.label("Environment Name: ");
+                        ui.text_edit_singleline(&mut dialog.name);
+                        if ui.button("Add").clicked()
+                            && !dialog.name.is_empty()
+                            && !store.environments.iter().any(|e| e.name == dialog.name)
+                        {
+                            store.environments.push(Environment {
+                                name: dialog.name.clone(),
+                                variables: Vec::new(),
+                            });
+                            dialog.selected = Some(store.environments.len() - 1);
+                            dialog.name.clear();
+                            changed = true;
+                        }
+                    });
+                    ui.separator();
+
+                    ui.horizontal_top(|ui| {
+                        ui.vertical(|ui| {
+                            for (idx, env) in store.environments.iter().enumerate() {
+                                if ui
+                                    .selectable_label(dialog.selected == Some(idx), &env.name)
+                                    .clicked()
+                                {
+                                    dialog.selected = Some(idx);
+                                }
+                            }
+                        });
+                        ui.separator();
+
+                        ui.vertical(|ui| {
+                            let Some(idx) = dialog.selected else {
+                                ui.label("Select an environment");
+                                return;
+                            };
+                            let Some(env) = store.environments.get_mut(idx) else {
+                                return;
+                            };
+                            changed |= render_key_values(ui, &mut env.variables, "Add Variable");
+                            if ui.button("Delete Environment").clicked() {
+                                let removed = store.environments.remove(idx);
+                                if store.active.as_ref() == Some(&removed.name) {
+                                    store.active = None;
+                                }
+                                dialog.selected = None;
+                                changed = true;
+                            }
+                        });
+                    });
+
+                    ui.separator();
+                    if ui.button("Close").clicked() {
+                        dialog.show = false;
+                    }
+
+                    if changed {
+                        store.save();
+                    }
+                });
+        }
+    }
+}
+
+// 서명과 Digest 인증을 보낼 때마다 새로 계산하는 요청.
+// 이벤트 스트림을 다시 연결할 때도 오래된 서명 대신 새 서명으로 보낸다
+struct PreparedRequest {
+    client: Client,
+    method: Method,
+    url: String,
+    headers: HeaderMap,
+    auth: Auth,
+    body: RequestBody,
+    // 서명과 Digest 계산에 쓰는 바디 내용
+    contents: Vec<u8>,
+}
+
+impl PreparedRequest {
+    async fn send(
+        &self,
+        extra_headers: HeaderMap,
+        tx: &Sender<ApiEvent>,
+    ) -> Result<reqwest::Response, String> {
+        let mut headers = self.headers.clone();
+        headers.extend(extra_headers);
+
+        // 변수 치환과 인증 적용이 끝난 최종 요청에 서명
+        let signed = match &self.auth {
+            Auth::AwsSigV4(config) => Some(
+                reqwest::Url::parse(&self.url)
+                    .map_err(|e| e.to_string())
+                    .and_then(|url| {
+                        sigv4::sign(
+                            config,
+                            &self.method,
+                            &url,
+                            &headers,
+                            &self.contents,
+                            chrono::Utc::now(),
+                        )
+                    }),
+            ),
+            Auth::Hmac(config) => Some(
+                reqwest::Url::parse(&self.url)
+                    .map_err(|e| e.to_string())
+                    .and_then(|url| {
+                        hmac_signature::sign(
+                            config,
+                            &self.method,
+                            &url,
+                            &self.contents,
+                            oauth2::now_secs(),
+                        )
+                    }),
+            ),
+            _ => None,
+        };
+        if let Some(signed) = signed {
+            let signed_headers = signed.map_err(|e| format!("Failed to sign request: {}", e))?;
+            for (key, value) in signed_headers {
+                if let (Ok(name), Ok(value)) =
+                    (HeaderName::from_bytes(key.as_bytes()), value.parse())
+                {
+                    headers.insert(name, value);
+                }
+            }
+        }
+
+        let request = self
+            .client
+            .request(self.method.clone(), &self.url)
+            .headers(headers.clone());
+        let response = attach_body(request, &self.body, tx)
+            .await?
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        // Digest 인증은 401 챌린지를 받은 뒤 같은 요청을 다시 보낸다
+        let Auth::Digest { username, password } = &self.auth else {
+            return Ok(response);
+        };
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+        let Some(challenge) = response
+            .headers()
+            .get_all(WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .find_map(digest::Challenge::parse)
+        else {
+            return Ok(response);
+        };
+        // 리다이렉트 후의 주소가 아니라 다시 보낼 요청의 주소로 digest-uri를 만든다
+        let uri = match reqwest::Url::parse(&self.url) {
+            Ok(url) => match url.query() {
+                Some(query) => format!("{}?{}", url.path(), query),
+                None => url.path().to_string(),
+            },
+            Err(_) => "/".to_string(),
+        };
+        let authorization = challenge.authorization(
+            username,
+            password,
+            self.method.as_str(),
+            &uri,
+            &self.contents,
+        );
+        // multipart와 파일 바디는 복제할 수 없으므로 요청을 다시 만든다
+        let retry_request = self
+            .client
+            .request(self.method.clone(), &self.url)
+            .headers(headers)
+            .header(AUTHORIZATION, authorization);
+        attach_body(retry_request, &self.body, tx)
+            .await?
+            .send()
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+// text/event-stream 응답을 이벤트 단위로 UI에 보낸다.
+// 재연결 요청이 있으면 스트림이 끊길 때 retry만큼 기다렸다가 Last-Event-ID를 붙여 다시 연결한다.
+// UI에서 취소한 경우에는 상태를 UI가 이미 정리했으므로 아무것도 보내지 않는다
+async fn stream_events(
+    response: reqwest::Response,
+    request: &PreparedRequest,
+    reconnect: bool,
+    tx: &Sender<ApiEvent>,
+    start_time: std::time::Instant,
+) {
+    let cancel = CancellationToken::new();
+    let _ = tx.send(ApiEvent::EventStreamOpened(
+        ApiResponse {
+            method: request.method.to_string(),
+            status: response.status().as_u16(),
+            headers: response.headers().clone(),
+            body: String::new(),
+            time_taken: start_time.elapsed(),
+            events: Vec::new(),
+        },
+        cancel.clone(),
+    ));
+    let close = |reason: &str| {
+        let _ = tx.send(ApiEvent::EventStreamClosed(reason.to_string()));
+    };
+
+    let mut parser = sse::Parser::default();
+    let mut current = Ok(response);
+    loop {
+        let reason = match current {
+            Ok(response) => {
+                let mut stream = response.bytes_stream();
+                loop {
+                    tokio::select! {
+                        _ = cancel.cancelled() => return,
+                        chunk = stream.next() => match chunk {
+                            Some(Ok(chunk)) => {
+                                for event in parser.feed(&chunk) {
+                                    let _ = tx.send(ApiEvent::EventStreamEvent(event));
+                                }
+                            }
+                            Some(Err(e)) => break format!("Stream error: {}", e),
+                            None => break "Stream ended".to_string(),
+                        },
+                    }
+                }
+            }
+            Err(reason) => reason,
+        };
+
+        if !reconnect {
+            return close(&reason);
+        }
+        let retry = parser.retry_ms.unwrap_or(sse::DEFAULT_RETRY_MS);
+        let _ = tx.send(ApiEvent::EventStreamStatus(format!(
+            "{}, reconnecting in {} ms",
+            reason, retry
+        )));
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            _ = tokio::time::sleep(Duration::from_millis(retry)) => {}
+        }
+
+        // 서명과 Digest 인증은 새 연결마다 다시 계산된다
+        let mut last_event_id = HeaderMap::new();
+        if let Some(id) = &parser.last_event_id {
+            match HeaderValue::from_str(id) {
+                Ok(value) => {
+                    last_event_id.insert("Last-Event-ID", value);
+                }
+                Err(_) => return close("Invalid Last-Event-ID"),
+            }
+        }
+        current = match request.send(last_event_id, tx).await {
+            Ok(response) if sse::is_event_stream(response.headers()) => {
+                let _ = tx.send(ApiEvent::EventStreamStatus(
+                    "Streaming events...".to_string(),
+                ));
+                Ok(response)
+            }
+            Ok(response) => {
+                return close(&format!(
+                    "Reconnect failed with status {}",
+                    response.status()
+                ))
+            }
+            Err(e) => Err(format!("Reconnect failed: {}", e)),
+        };
+    }
+}
+
+// 요청의 TLS 설정을 따르는 클라이언트. 토큰 발급과 스키마 조회도 같은 설정을 쓴다
+fn http_client(insecure: bool) -> Client {
+    Client::builder()
+        .danger_accept_invalid_certs(insecure)
+        .build()
+        .unwrap_or_default()
+}
+
+// 변수 우선순위: 요청 > 그룹 > 활성 환경
+fn scoped_variables(
+    environment: Option<&Environment>,
+    group: Option<&RequestGroup>,
+    request: &ApiRequest,
+) -> HashMap<String, String> {
+    let mut scopes = Vec::new();
+    if let Some(env) = environment {
+        scopes.push(&env.variables);
+    }
+    if let Some(group) = group {
+        scopes.push(&group.variables);
+    }
+    scopes.push(&request.variables);
+
+    let mut variables = HashMap::new();
+    for (key, value) in scopes.into_iter().flatten() {
+        if !key.is_empty() {
+            variables.insert(key.clone(), value.clone());
+        }
+    }
+    variables
+}
+
+// URL, 헤더, 바디의 {{변수}}를 치환한 요청을 만든다
+fn resolve_request(
+    request: &ApiRequest,
+    group: Option<&RequestGroup>,
+    environment: Option<&Environment>,
+) -> Result<ApiRequest, String> {
+    let variables = scoped_variables(environment, group, request);
+    let mut resolver = Resolver::new(&variables);
+    let mut req = request.clone();
+
+    // 비활성 헤더는 보내지 않는다
+    req.headers.retain(|header| header.enabled);
+
+    // 그룹 기본 헤더 중 요청에서 같은 이름으로 덮어쓰지 않은 것만 상속
+    if let Some(group) = group {
+        let mut headers: Vec<KeyValue> = group
+            .headers
+            .iter()
+            .filter(|header| {
+                header.enabled
+                    && !req
+                        .headers
+                        .iter()
+                        .any(|own| own.key.eq_ignore_ascii_case(&header.key))
+            })
+            .cloned()
+            .collect();
+        headers.append(&mut req.headers);
+        req.headers = headers;
+    }
+
+    // 쿼리는 파라미터 단위로 치환한 뒤 퍼센트 인코딩
+    let (base, query) = split_url(&req.url);
+    let path_values: Vec<(String, String)> = req
+        .path_params
+        .iter()
+        .map(|p| (p.key.clone(), resolver.resolve(&p.value)))
+        .collect();
+    let mut url = substitute_path_params(&resolver.resolve(base), &path_values)?;
+    if let Some(query) = query {
+        let pairs: Vec<(String, Option<String>)> = parse_query(query)
+            .into_iter()
+            .map(|(key, value)| {
+                (
+                    resolver.resolve(&key),
+                    value.map(|value| resolver.resolve(&value)),
+                )
+            })
+            .collect();
+        if !pairs.is_empty() {
+            url.push('?');
+            url.push_str(&encode_query(&pairs));
+        }
+    }
+    req.url = url;
+    for header in req.headers.iter_mut() {
+        header.key = resolver.resolve(&header.key);
+        header.value = resolver.resolve(&header.value);
+    }
+    if req.kind == RequestKind::GraphQl {
+        // GraphQL 요청은 쿼리와 변수로 JSON 바디를 만들어 POST로 보낸다
+        req.graphql.query = resolver.resolve(&req.graphql.query);
+        req.graphql.variables = resolver.resolve(&req.graphql.variables);
+        req.method = "POST".to_string();
+        req.body_type = BodyType::Json;
+        req.body = req.graphql.body()?;
+    } else if req.kind == RequestKind::JsonRpc {
+        // JSON-RPC 요청도 id를 붙인 JSON 바디로 POST한다
+        for call in req.jsonrpc.calls.iter_mut() {
+            call.method = resolver.resolve(&call.method);
+            call.params = resolver.resolve(&call.params);
+        }
+        req.method = "POST".to_string();
+        req.body_type = BodyType::Json;
+        req.body = req.jsonrpc.body()?;
+    } else if req.kind == RequestKind::Grpc {
+        req.grpc.message = resolver.resolve(&req.grpc.message);
+        for file in req.grpc.proto_files.iter_mut() {
+            *file = resolver.resolve(file);
+        }
+    } else {
+        // 편집기에서 바디를 숨기는 메소드는 바디 없이 보낸다
+        if BODYLESS_METHODS.contains(&req.method.as_str()) {
+            req.body_type = BodyType::None;
+        }
+        match req.body_type {
+            BodyType::None => req.body.clear(),
+            BodyType::FormData => {
+                req.body.clear();
+                req.form_data.retain(|part| part.enabled);
+                for part in req.form_data.iter_mut() {
+                    part.key = resolver.resolve(&part.key);
+                    part.value = resolver.resolve(&part.value);
+                    part.file_path = resolver.resolve(&part.file_path);
+                    part.file_name = resolver.resolve(&part.file_name);
+                    part.content_type = resolver.resolve(&part.content_type);
+                }
+            }
+            BodyType::FormUrlEncoded if !req.url_encoded_raw => {
+                let pairs: Vec<(String, String)> = req
+                    .url_encoded
+                    .iter()
+                    .filter(|item| item.enabled && !item.key.is_empty())
+                    .map(|item| (resolver.resolve(&item.key), resolver.resolve(&item.value)))
+                    .collect();
+                req.body = encode_form(&pairs);
+            }
+            BodyType::Binary => {
+                req.body.clear();
+                req.binary_path = resolver.resolve(&req.binary_path);
+            }
+            _ => req.body = resolver.resolve(&req.body),
+        }
+    }
+
+    // Inherit이면 그룹의 기본 인증을 사용. 그룹이 없거나 그룹도 Inherit이면 인증 없음
+    if req.auth == Auth::Inherit {
+        req.auth = group
+            .map(|group| group.auth.clone())
+            .filter(|auth| *auth != Auth::Inherit)
+            .unwrap_or(Auth::None);
+    }
+    req.auth = req.auth.resolve(&mut resolver);
+
+    resolver.finish()?;
+    Ok(req)
+}
+
+// 표준 메소드 외에도 토큰 규칙에 맞으면 그대로 보낸다
+fn parse_method(method: &str) -> Result<Method, String> {
+    Method::from_bytes(method.as_bytes()).map_err(|_| format!("Invalid HTTP method '{}'", method))
+}
+
+// 사용자가 지정한 Content-Type을 우선하고, 없으면 바디 종류에 맞는 값을 채운다.
+// multipart는 boundary가 든 Content-Type을 따로 붙이므로 사용자 값은 쓰지 않는다
+fn set_content_type(headers: &mut HeaderMap, body_type: BodyType, has_body: bool) {
+    if body_type == BodyType::FormData {
+        headers.remove(CONTENT_TYPE);
+    }
+    if has_body && !headers.contains_key(CONTENT_TYPE) {
+        if let Some(content_type) = body_type.content_type() {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+        }
+    }
+}
+
+// 읽을 수 없는 저장 파일을 시각을 붙인 이름으로 옮기고 새 이름을 반환
+fn back_up_file(path: &str) -> Result<String, String> {
+    let backup = format!(
+        "{}.{}.bak",
+        path,
+        chrono::Local::now().format("%Y%m%d%H%M%S")
+    );
+    fs::rename(path, &backup).map_err(|e| format!("Failed to back up {}: {}", path, e))?;
+    Ok(backup)
+}
+
+// 기록 항목들을 HAR 파일로 저장
+fn write_har(entries: &[&HistoryEntry], file_path: &Path) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&har::export(entries))
+        .map_err(|e| format!("Failed to serialize HAR: {}", e))?;
+    fs::write(file_path, json).map_err(|e| format!("Failed to write file: {}", e))
+}
+
+// 이름이나 값이 비어 있거나 유효하지 않은 헤더는 건너뛴다
+fn header_map(headers: &[KeyValue]) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    for KeyValue { key, value, .. } in headers {
+        if !key.is_empty() && !value.is_empty() {
+            if let Ok(header_name) = HeaderName::from_bytes(key.as_bytes()) {
+                if let Ok(header_value) = value.parse() {
+                    map.insert(header_name, header_value);
+                }
+            }
+        }
+    }
+    map
+}
+
+// 요청에 바디를 붙인다. 파일 바디는 디스크에서 스트리밍하며 업로드 진행률을 보낸다
+async fn attach_body(
+    request: RequestBuilder,
+    body: &RequestBody,
+    tx: &Sender<ApiEvent>,
+) -> Result<RequestBuilder, String> {
+    match body {
+        RequestBody::Bytes(bytes) if bytes.is_empty() => Ok(request),
+        RequestBody::Bytes(bytes) => Ok(request.body(bytes.clone())),
+        RequestBody::FormData(parts) => Ok(request.multipart(body::build_form(parts).await?)),
+        RequestBody::File(path) => {
+            let file = tokio::fs::File::open(path)
+                .await
+                .map_err(|e| format!("Failed to open '{}': {}", path, e))?;
+            let total = file.metadata().await.map(|m| m.len()).unwrap_or(0);
+            let tx = tx.clone();
+            let mut sent = 0;
+            let _ = tx.send(ApiEvent::UploadProgress { sent, total });
+            let stream = ReaderStream::new(file).inspect_ok(move |chunk| {
+                sent += chunk.len() as u64;
+                let _ = tx.send(ApiEvent::UploadProgress { sent, total });
+            });
+            // 사용자가 넣은 Content-Length가 있어도 실제 파일 크기 하나만 보낸다
+            let mut length = HeaderMap::new();
+            length.insert(CONTENT_LENGTH, HeaderValue::from(total));
+            Ok(request.headers(length).body(Body::wrap_stream(stream)))
+        }
+    }
+}
+
+// {{변수}} 하이라이트가 적용된 텍스트 입력
+fn variable_text_edit(
+    ui: &mut Ui,
+    text: &mut String,
+    variables: &HashMap<String, String>,
+    multiline: bool,
+) -> egui::Response {
+    let mut layouter = |ui: &Ui, text: &str, wrap_width: f32| {
+        let font_id = egui::TextStyle::Body.resolve(ui.style());
+        let job = variable_layout_job(
+            text,
+            variables,
+            font_id,
+            ui.visuals().widgets.inactive.text_color(),
+            wrap_width,
+        );
+        ui.fonts(|f| f.layout_job(job))
+    };
+
+    let edit = if multiline {
+        egui::TextEdit::multiline(text)
+    } else {
+        egui::TextEdit::singleline(text)
+    };
+    ui.add(edit.layouter(&mut layouter))
+}
+
+// 활성화 체크박스와 설명이 있는 키/값 표. 변경 여부를 반환한다
+fn render_key_value_table(ui: &mut Ui, items: &mut Vec<KeyValue>, add_label: &str) -> bool {
+    let mut changed = false;
+    let mut to_remove = None;
+
+    egui::Grid::new(add_label)
+        .num_columns(5)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("");
+            ui.label("Key");
+            ui.label("Value");
+            ui.label("Description");
+            ui.end_row();
+
+            for (idx, item) in items.iter_mut().enumerate() {
+                changed |= ui.checkbox(&mut item.enabled, "").changed();
+                changed |= ui.text_edit_singleline(&mut item.key).changed();
+                changed |= ui.text_edit_singleline(&mut item.value).changed();
+                changed |= ui.text_edit_singleline(&mut item.description).changed();
+                if ui.button("❌").clicked() {
+                    to_remove = Some(idx);
+                }
+                ui.end_row();
+            }
+        });
+
+    if let Some(idx) = to_remove {
+        items.remove(idx);
+        changed = true;
+    }
+
+    if ui.button(add_label).clicked() {
+        items.push(KeyValue::default());
+        changed = true;
+    }
+
+    changed
+}
+
+// 입력 중인 값과 맞는 후보를 아래에 팝업으로 보여주는 텍스트 입력
+fn autocomplete_text_edit(
+    ui: &mut Ui,
+    text: &mut String,
+    hints: &[&str],
+    variables: &HashMap<String, String>,
+) -> egui::Response {
+    let mut response = variable_text_edit(ui, text, variables, false);
+    let popup_id = response.id.with("autocomplete");
+
+    let query = text.to_ascii_lowercase();
+    let matches: Vec<&str> = hints
+        .iter()
+        .copied()
+        .filter(|hint| {
+            let hint = hint.to_ascii_lowercase();
+            hint != query && (query.is_empty() || hint.contains(&query))
+        })
+        .take(8)
+        .collect();
+
+    if response.has_focus() && !matches.is_empty() {
+        ui.memory_mut(|mem| mem.open_popup(popup_id));
+    }
+
+    let selected = egui::popup::popup_below_widget(
+        ui,
+        popup_id,
+        &response,
+        egui::PopupCloseBehavior::CloseOnClickOutside,
+        |ui| {
+            ui.set_min_width(150.0);
+            let mut selected = None;
+            for hint in &matches {
+                if ui.selectable_label(false, *hint).clicked() {
+                    selected = Some(*hint);
+                }
+            }
+            selected
+        },
+    )
+    .flatten();
+
+    if let Some(hint) = selected {
+        *text = hint.to_string();
+        response.mark_changed();
+        ui.memory_mut(|mem| mem.close_popup());
+    }
+
+    response
+}
+
+// 활성화 체크박스, 설명, 이름/값 자동완성이 있는 헤더 표. 변경 여부를 반환한다
+fn render_header_table(
+    ui: &mut Ui,
+    headers: &mut Vec<KeyValue>,
+    variables: &HashMap<String, String>,
+) -> bool {
+    let mut changed = false;
+    let mut to_remove = None;
+
+    egui::Grid::new("headers")
+        .num_columns(5)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("");
+            ui.label("Key");
+            ui.label("Value");
+            ui.label("Description");
+            ui.end_row();
+
+            for (idx, header) in headers.iter_mut().enumerate() {
+                changed |= ui.checkbox(&mut header.enabled, "").changed();
+                changed |=
+                    autocomplete_text_edit(ui, &mut header.key, HEADER_NAMES, variables).changed();
+                let value_hints = header_value_hints(&header.key);
+                changed |=
+                    autocomplete_text_edit(ui, &mut header.value, value_hints, variables).changed();
+                changed |= ui.text_edit_singleline(&mut header.description).changed();
+                if ui.button("❌").clicked() {
+                    to_remove = Some(idx);
+                }
+                ui.end_row();
+            }
+        });
+
+    if let Some(idx) = to_remove {
+        headers.remove(idx);
+        changed = true;
+    }
+
+    if ui.button("Add Header").clicked() {
+        headers.push(KeyValue::default());
+        changed = true;
+    }
+
+    changed
+}
+
+// 메시지 작성과 주고받은 메시지 로그. 설정 변경 여부를 반환한다
+fn render_websocket(
+    ui: &mut Ui,
+    config: &mut WebSocketConfig,
+    session: &mut websocket::Session,
+) -> bool {
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        ui.label("Subprotocols:");
+        changed |= ui
+            .add_enabled(
+                session.commands.is_none(),
+                egui::TextEdit::singleline(&mut config.subprotocols).hint_text("graphql-ws, chat"),
+            )
+            .changed();
+    });
+    if let Some(status) = &session.status {
+        ui.label(status);
+    }
+    ui.separator();
+
+    ui.horizontal(|ui| {
+        changed |= ui
+            .selectable_value(&mut config.binary, false, "Text")
+            .changed();
+        changed |= ui
+            .selectable_value(&mut config.binary, true, "Binary (hex)")
+            .changed();
+    });
+    changed |= ui
+        .add(egui::TextEdit::multiline(&mut config.message).desired_width(f32::INFINITY))
+        .changed();
+    ui.horizontal(|ui| {
+        let send = ui.add_enabled(session.connected, egui::Button::new("Send Message"));
+        if send.clicked() {
+            if let Err(e) = session.send(&config.message, config.binary) {
+                session.status = Some(e);
+            }
+        }
+        if ui.button("Clear Log").clicked() {
+            session.log.clear();
+        }
+    });
+    ui.separator();
+
+    ScrollArea::vertical()
+        .id_salt("websocket_log")
+        .max_height(400.0)
+        .stick_to_bottom(true)
+        .show(ui, |ui| {
+            for entry in &session.log {
+                let (arrow, color) = match entry.direction {
+                    Direction::Sent => ("↑", Color32::LIGHT_BLUE),
+                    Direction::Received => ("↓", Color32::GREEN),
+                };
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(RichText::new(&entry.timestamp).weak());
+                    ui.label(RichText::new(arrow).color(color));
+                    if entry.binary {
+                        ui.label(RichText::new("[binary]").weak());
+                    }
+                    ui.label(&entry.content);
+                });
+            }
+        });
+
+    changed
+}
+
+// JSON-RPC 호출 목록 편집. 변경 여부를 반환한다
+fn render_jsonrpc(ui: &mut Ui, jsonrpc: &mut JsonRpcRequest) -> bool {
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        ui.label(format!("Next id: {}", jsonrpc.next_id));
+        if ui.button("Reset").clicked() {
+            jsonrpc.next_id = 1;
+            changed = true;
+        }
+        let enabled = jsonrpc.calls.iter().filter(|call| call.enabled).count();
+        if enabled > 1 {
+            ui.label(RichText::new(format!("Batch of {} calls", enabled)).weak());
+        }
+    });
+
+    let mut remove = None;
+    for (idx, call) in jsonrpc.calls.iter_mut().enumerate() {
+        ui.separator();
+        ui.horizontal(|ui| {
+            changed |= ui.checkbox(&mut call.enabled, "").changed();
+            ui.label("Method:");
+            changed |= ui
+                .add(egui::TextEdit::singleline(&mut call.method).hint_text("eth_blockNumber"))
+                .changed();
+            changed |= ui
+                .checkbox(&mut call.notification, "Notification")
+                .changed();
+            if ui.button("🗑").clicked() {
+                remove = Some(idx);
+            }
+        });
+        changed |= ui
+            .add(
+                egui::TextEdit::multiline(&mut call.params)
+                    .id_salt(("jsonrpc_params", idx))
+                    .hint_text("Params: [] or {}")
+                    .code_editor()
+                    .desired_rows(3)
+                    .desired_width(f32::INFINITY),
+            )
+            .changed();
+    }
+    if let Some(idx) = remove {
+        jsonrpc.calls.remove(idx);
+        changed = true;
+    }
+
+    if ui.button("Add Call").clicked() {
+        jsonrpc.calls.push(JsonRpcCall::default());
+        changed = true;
+    }
+
+    changed
+}
+
+// 보낸 요청과 id로 맞춘 응답 목록. error 객체는 빨간색으로 표시한다
+fn render_jsonrpc_responses(ui: &mut Ui, body: &Value, sent: &[(u64, String)]) {
+    let (responses, missing) = jsonrpc::match_responses(body, sent);
+
+    for response in responses {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("id: {}", response.id)).strong());
+            match &response.method {
+                Some(method) => ui.label(method),
+                None => ui.label(RichText::new("unknown id").color(Color32::YELLOW)),
+            };
+        });
+        match &response.outcome {
+            Ok(result) => {
+                ui.label(serde_json::to_string_pretty(result).unwrap_or_default());
+            }
+            Err(error) => {
+                let name = jsonrpc::error_name(error.code)
+                    .map(|name| format!(" ({})", name))
+                    .unwrap_or_default();
+                ui.label(
+                    RichText::new(format!("Error {}{}: {}", error.code, name, error.message))
+                        .color(Color32::RED),
+                );
+                if let Some(data) = &error.data {
+                    ui.label(
+                        RichText::new(serde_json::to_string_pretty(data).unwrap_or_default())
+                            .color(Color32::RED),
+                    );
+                }
+            }
+        }
+        ui.separator();
+    }
+
+    for id in missing {
+        ui.label(RichText::new(format!("No response for id {}", id)).color(Color32::YELLOW));
+    }
+}
+
+#[derive(Default)]
+struct GrpcResponse {
+    changed: bool,
+    load: bool,
+}
+
+// 서비스 정의 불러오기, 서비스/메소드 선택, 요청 메시지 편집
+fn render_grpc(ui: &mut Ui, grpc: &mut GrpcRequest, session: &grpc::Session) -> GrpcResponse {
+    let mut response = GrpcResponse::default();
+
+    response.changed |= ui
+        .checkbox(&mut grpc.use_reflection, "Use server reflection")
+        .changed();
+    if !grpc.use_reflection {
+        ui.label("Proto files (.proto needs protoc, or a descriptor set)");
+        let mut remove = None;
+        for (idx, file) in grpc.proto_files.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(file);
+                if ui.button("🗑").clicked() {
+                    remove = Some(idx);
+                }
+            });
+        }
+        if let Some(idx) = remove {
+            grpc.proto_files.remove(idx);
+            response.changed = true;
+        }
+        if ui.button("Add File").clicked() {
+            if let Some(paths) = FileDialog::new()
+                .add_filter("Protocol Buffers", &["proto", "protoset", "pb", "desc"])
+                .pick_files()
+            {
+                grpc.proto_files
+                    .extend(paths.iter().map(|path| path.display().to_string()));
+                response.changed = true;
+            }
+        }
+    }
+    response.load = ui.button("Load Services").clicked();
+    ui.separator();
+
+    if let Some(pool) = grpc.pool.clone() {
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label("Service")
+                .selected_text(&grpc.service)
+                .show_ui(ui, |ui| {
+                    for service in pool.services() {
+                        let name = service.full_name().to_string();
+                        if ui
+                            .selectable_value(&mut grpc.service, name.clone(), &name)
+                            .changed()
+                        {
+                            grpc.method.clear();
+                            response.changed = true;
+                        }
+                    }
+                });
+            let methods = pool
+                .get_service_by_name(&grpc.service)
+                .map(|service| service.methods().collect::<Vec<_>>())
+                .unwrap_or_default();
+            egui::ComboBox::from_label("Method")
+                .selected_text(&grpc.method)
+                .show_ui(ui, |ui| {
+                    for method in methods {
+                        let name = method.name().to_string();
+                        let label = format!("{} ({})", name, grpc::method_kind(&method));
+                        response.changed |=
+                            ui.selectable_value(&mut grpc.method, name, label).changed();
+                    }
+                });
+        });
+    }
+
+    let method = grpc.method_descriptor();
+    ui.horizontal(|ui| {
+        match &method {
+            Some(method) => ui.label(format!(
+                "{} → {}",
+                method.input().full_name(),
+                method.output().full_name()
+            )),
+            None => ui.label("Message (JSON)"),
+        };
+        if let Some(method) = &method {
+            if ui.button("Template").clicked() {
+                grpc.message = grpc::message_template(method);
+                response.changed = true;
+            }
+        }
+    });
+    if method
+        .as_ref()
+        .is_some_and(|method| method.is_client_streaming())
+    {
+        ui.label(RichText::new("Client streaming: enter a JSON array of messages").weak());
+    }
+    response.changed |= ui
+        .add_enabled(
+            session.cancel.is_none(),
+            egui::TextEdit::multiline(&mut grpc.message)
+                .code_editor()
+                .desired_rows(8)
+                .desired_width(f32::INFINITY),
+        )
+        .changed();
+
+    response
+}
+
+// 상태 코드, 응답 메타데이터와 트레일러, 받은 메시지 목록
+fn render_grpc_response(ui: &mut Ui, session: &grpc::Session) {
+    let Some(status) = &session.status else {
+        return;
+    };
+    ui.separator();
+    ui.heading("Response");
+    ui.label(format!("Status: {}", status));
+
+    for (title, metadata) in [
+        ("Metadata", &session.headers),
+        ("Trailers", &session.trailers),
+    ] {
+        if !metadata.is_empty() {
+            ui.collapsing(title, |ui| {
+                for (key, value) in metadata {
+                    ui.label(format!("{}: {}", key, value));
+                }
+            });
+        }
+    }
+
+    ui.label(format!("Messages ({})", session.messages.len()));
+    ScrollArea::vertical()
+        .id_salt("grpc_messages")
+        .max_height(400.0)
+        .stick_to_bottom(true)
+        .show(ui, |ui| {
+            for message in &session.messages {
+                ui.add(
+                    egui::TextEdit::multiline(&mut message.as_str())
+                        .code_editor()
+                        .desired_width(f32::INFINITY),
+                );
+            }
+        });
+}
+
+#[derive(Default)]
+struct GraphQlResponse {
+    changed: bool,
+    fetch_schema: bool,
+}
+
+// GraphQL 쿼리/변수 편집기. 스키마가 있으면 필드 자동완성과 스키마 트리를 보여준다
+fn render_graphql(
+    ui: &mut Ui,
+    graphql: &mut GraphQlRequest,
+    variables: &HashMap<String, String>,
+) -> GraphQlResponse {
+    let mut response = GraphQlResponse::default();
+
+    ui.label("Query");
+    let query_id = ui.make_persistent_id("graphql_query");
+    let mut output = egui::TextEdit::multiline(&mut graphql.query)
+        .id(query_id)
+        .code_editor()
+        .desired_rows(10)
+        .desired_width(f32::INFINITY)
+        .show(ui);
+    response.changed |= output.response.changed();
+
+    // 후보 버튼을 누르면 포커스가 빠지므로 마지막 커서 위치를 기억해 둔다
+    if let Some(range) = output.cursor_range {
+        ui.data_mut(|d| d.insert_temp(query_id, range.primary.ccursor.index));
+    }
+    let cursor: Option<usize> = ui.data(|d| d.get_temp(query_id));
+
+    if let (Some(schema), Some(cursor)) = (&graphql.schema, cursor) {
+        let byte_cursor = graphql
+            .query
+            .char_indices()
+            .nth(cursor)
+            .map(|(idx, _)| idx)
+            .unwrap_or(graphql.query.len());
+        let (start, fields) = schema.completions(&graphql.query, byte_cursor);
+        let mut selected = None;
+        if !fields.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                for field in fields.iter().take(12) {
+                    if ui
+                        .small_button(&field.name)
+                        .on_hover_text(field.signature())
+                        .clicked()
+                    {
+                        selected = Some(field.name.clone());
+                    }
+                }
+            });
+        }
+        if let Some(name) = selected {
+            graphql.query.replace_range(start..byte_cursor, &name);
+            let cursor = graphql.query[..start + name.len()].chars().count();
+            output
+                .state
+                .cursor
+                .set_char_range(Some(egui::text::CCursorRange::one(
+                    egui::text::CCursor::new(cursor),
+                )));
+            output.state.store(ui.ctx(), query_id);
+            ui.data_mut(|d| d.insert_temp(query_id, cursor));
+            ui.memory_mut(|mem| mem.request_focus(query_id));
+            response.changed = true;
+        }
+    }
+
+    ui.label("Variables (JSON)");
+    response.changed |= variable_text_edit(ui, &mut graphql.variables, variables, true).changed();
+    if !graphql.variables.trim().is_empty()
+        && serde_json::from_str::<Value>(&graphql.variables).is_err()
+    {
+        ui.label(RichText::new("Variables must be a JSON object").color(Color32::RED));
+    }
+
+    let operations = graphql::operation_names(&graphql.query);
+    if operations.len() > 1 || graphql.operation_name.is_some() {
+        let previous = graphql.operation_name.clone();
+        egui::ComboBox::from_label("Operation")
+            .selected_text(graphql.operation_name.as_deref().unwrap_or("(none)"))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut graphql.operation_name, None, "(none)");
+                for name in &operations {
+                    ui.selectable_value(&mut graphql.operation_name, Some(name.clone()), name);
+                }
+            });
+        response.changed |= previous != graphql.operation_name;
+    }
+
+    ui.separator();
+    if ui.button("Fetch Schema").clicked() {
+        response.fetch_schema = true;
+    }
+    if let Some(schema) = &graphql.schema {
+        ui.collapsing("Schema", |ui| {
+            ScrollArea::vertical()
+                .id_salt("graphql_schema")
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for (label, type_name) in schema.root_types() {
+                        egui::CollapsingHeader::new(format!("{}: {}", label, type_name))
+                            .id_salt(("graphql_root", type_name))
+                            .show(ui, |ui| render_schema_type(ui, schema, type_name, 0));
+                    }
+                    egui::CollapsingHeader::new("All Types")
+                        .id_salt("graphql_all_types")
+                        .show(ui, |ui| {
+                            for ty in &schema.types {
+                                let Some(name) = &ty.name else { continue };
+                                if name.starts_with("__") {
+                                    continue;
+                                }
+                                egui::CollapsingHeader::new(format!("{} ({})", name, ty.kind))
+                                    .id_salt(("graphql_type", name))
+                                    .show(ui, |ui| render_schema_type(ui, schema, name, 0));
+                            }
+                        });
+                });
+        });
+    }
+
+    response
+}
+
+// 타입의 필드를 나열한다. 객체 타입 필드는 펼쳐서 하위 필드를 볼 수 있다
+fn render_schema_type(ui: &mut Ui, schema: &Schema, type_name: &str, depth: usize) {
+    let Some(ty) = schema.find_type(type_name) else {
+        return;
+    };
+    if let Some(description) = &ty.description {
+        ui.label(RichText::new(description).italics());
+    }
+    for field in ty.fields.iter().flatten() {
+        let child = field.ty.named();
+        let has_fields = schema
+            .find_type(child)
+            .is_some_and(|ty| ty.fields.as_ref().is_some_and(|f| !f.is_empty()));
+        if has_fields && depth < 8 {
+            let header = egui::CollapsingHeader::new(field.signature())
+                .id_salt(ui.id().with((type_name, &field.name)))
+                .show(ui, |ui| render_schema_type(ui, schema, child, depth + 1));
+            if let Some(description) = &field.description {
+                header.header_response.on_hover_text(description);
+            }
+        } else {
+            let label = ui.label(field.signature());
+            if let Some(description) = &field.description {
+                label.on_hover_text(description);
+            }
+        }
+    }
+    for field in ty.input_fields.iter().flatten() {
+        ui.label(format!("{}: {}", field.name, field.ty.display()));
+    }
+    for value in ty.enum_values.iter().flatten() {
+        ui.label(&value.name);
+    }
+}
+
+// x-www-form-urlencoded 바디 편집. 표와 원문 사이를 오갈 때 내용을 변환한다
+fn render_url_encoded(
+    ui: &mut Ui,
+    request: &mut ApiRequest,
+    variables: &HashMap<String, String>,
+) -> bool {
+    let mut raw = request.url_encoded_raw;
+    ui.horizontal(|ui| {
+        ui.selectable_value(&mut raw, false, "Table");
+        ui.selectable_value(&mut raw, true, "Raw");
+    });
+
+    if raw != request.url_encoded_raw {
+        if raw {
+            request.body = form_table_to_text(&request.url_encoded);
+        } else {
+            sync_key_values(decode_form(&request.body), &mut request.url_encoded);
+        }
+        request.url_encoded_raw = raw;
+        return true;
+    }
+
+    if raw {
+        variable_text_edit(ui, &mut request.body, variables, true).changed()
+    } else {
+        render_key_value_table(ui, &mut request.url_encoded, "Add Field")
+    }
+}
+
+// 바이너리 바디로 보낼 파일 선택. 파일 내용은 보낼 때 스트리밍한다
+fn render_binary_body(ui: &mut Ui, path: &mut String, variables: &HashMap<String, String>) -> bool {
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        ui.label("File:");
+        changed |= variable_text_edit(ui, path, variables, false).changed();
+        if ui.button("Choose File").clicked() {
+            if let Some(picked) = FileDialog::new().pick_file() {
+                *path = picked.to_string_lossy().into_owned();
+                changed = true;
+            }
+        }
+    });
+    if let Ok(metadata) = fs::metadata(&*path) {
+        ui.label(format!("{} bytes", metadata.len()));
+    }
+    changed
+}
+
+// multipart/form-data 파트 표. 파트마다 텍스트 또는 파일을 고를 수 있다
+fn render_form_data(
+    ui: &mut Ui,
+    parts: &mut Vec<FormPart>,
+    variables: &HashMap<String, String>,
+) -> bool {
+    let mut changed = false;
+    let mut to_remove = None;
+
+    egui::Grid::new("form_data")
+        .num_columns(7)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("");
+            ui.label("Key");
+            ui.label("Type");
+            ui.label("Value");
+            ui.label("Content-Type");
+            ui.label("File Name");
+            ui.end_row();
+
+            for (idx, part) in parts.iter_mut().enumerate() {
+                changed |= ui.checkbox(&mut part.enabled, "").changed();
+                changed |= variable_text_edit(ui, &mut part.key, variables, false).changed();
+                egui::ComboBox::from_id_salt(("form_part_kind", idx))
+                    .selected_text(match part.kind {
+                        FormPartKind::Text => "Text",
+                        FormPartKind::File => "File",
+                    })
+                    .show_ui(ui, |ui| {
+                        changed |= ui
+                            .selectable_value(&mut part.kind, FormPartKind::Text, "Text")
+                            .changed();
+                        changed |= ui
+                            .selectable_value(&mut part.kind, FormPartKind::File, "File")
+                            .changed();
+                    });
+                match part.kind {
+                    FormPartKind::Text => {
+                        changed |=
+                            variable_text_edit(ui, &mut part.value, variables, false).changed();
+                    }
+                    FormPartKind::File => {
+                        ui.horizontal(|ui| {
+                            changed |=
+                                variable_text_edit(ui, &mut part.file_path, variables, false)
+                                    .changed();
+                            if ui.button("Choose File").clicked() {
+                                if let Some(path) = FileDialog::new().pick_file() {
+                                    part.file_path = path.to_string_lossy().into_owned();
+                                    changed = true;
+                                }
+                            }
+                        });
+                    }
+                }
+                changed |= ui.text_edit_singleline(&mut part.content_type).changed();
+                changed |= ui.text_edit_singleline(&mut part.file_name).changed();
+                if ui.button("❌").clicked() {
+                    to_remove = Some(idx);
+                }
+                ui.end_row();
+            }
+        });
+
+    if let Some(idx) = to_remove {
+        parts.remove(idx);
+        changed = true;
+    }
+
+    if ui.button("Add Part").clicked() {
+        parts.push(FormPart::default());
+        changed = true;
+    }
+
+    changed
+}
+
+// URL에서 찾은 경로 파라미터 표. 이름은 URL에서만 바꿀 수 있다
+fn render_path_params(
+    ui: &mut Ui,
+    items: &mut [KeyValue],
+    variables: &HashMap<String, String>,
+) -> bool {
+    let mut changed = false;
+
+    egui::Grid::new("path_params")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("Key");
+            ui.label("Value");
+            ui.label("Description");
+            ui.end_row();
+
+            for item in items.iter_mut() {
+                ui.label(format!(":{}", item.key));
+                changed |= variable_text_edit(ui, &mut item.value, variables, false).changed();
+                changed |= ui.text_edit_singleline(&mut item.description).changed();
+                ui.end_row();
+            }
+        });
+
+    changed
+}
+
+// 키/값 목록 편집. 변경 여부를 반환한다
+fn render_key_values(ui: &mut Ui, items: &mut Vec<(String, String)>, add_label: &str) -> bool {
+    let mut changed = false;
+    let mut to_remove = None;
+
+    for (idx, (key, value)) in items.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            changed |= ui.text_edit_singleline(key).changed();
+            changed |= ui.text_edit_singleline(value).changed();
+            if ui.button("❌").clicked() {
+                to_remove = Some(idx);
+            }
+        });
+    }
+
+    if let Some(idx) = to_remove {
+        items.remove(idx);
+        changed = true;
+    }
+
+    if ui.button(add_label).clicked() {
+        items.push((String::new(), String::new()));
+        changed = true;
+    }
+
+    changed
+}
+
+impl eframe::App for ApiTester {
+    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        while let Ok(event) = self.rx.try_recv() {
+            match event {
+                ApiEvent::Response(response) => {
+                    self.record_history(&response);
+                    self.current_request.response = Some(response);
+                    self.is_loading = false;
+                    self.upload_progress = None;
+                }
+                ApiEvent::UploadProgress { sent, total } => {
+                    self.upload_progress = Some((sent, total));
+                }
+                ApiEvent::OAuthToken(target, token) => {
+                    self.oauth_status = Some("Access token updated".to_string());
+                    self.store_oauth_token(target, token);
+                }
+                ApiEvent::OAuthError(e) => {
+                    self.oauth_status = Some(e);
+                }
+                ApiEvent::GraphQlSchema { url, result } => match result {
+                    Ok(schema) => {
+                        self.graphql_status =
+                            Some(format!("Schema loaded ({} types)", schema.types.len()));
+                        // 가져오는 동안 다른 요청으로 바꿨다면 버린다
+                        if self.current_request.url == url {
+                            self.current_request.graphql.schema = Some(schema);
+                        }
+                    }
+                    Err(e) => self.graphql_status = Some(e),
+                },
+                // 이전 연결에서 늦게 도착한 이벤트는 버린다
+                ApiEvent::WebSocket(session_id, event) => {
+                    if session_id == self.websocket.id {
+                        self.websocket.handle(event);
+                    }
+                }
+                ApiEvent::GrpcDescriptors { name, result } => match result {
+                    Ok(pool) => {
+                        self.grpc_status =
+                            Some(format!("Loaded {} services", pool.services().len()));
+                        if self.current_request.name == name {
+                            self.current_request.grpc.pool = Some(pool);
+                        }
+                    }
+                    Err(e) => self.grpc_status = Some(e),
+                },
+                ApiEvent::Grpc(call_id, event) => {
+                    if call_id == self.grpc.id {
+                        self.grpc.handle(event);
+                    }
+                }
+                ApiEvent::EventStreamOpened(response, cancel) => {
+                    self.record_history(&response);
+                    self.current_request.response = Some(response);
+                    self.is_loading = false;
+                    self.upload_progress = None;
+                    self.event_stream = Some(cancel);
+                    self.event_stream_status = Some("Streaming events...".to_string());
+                }
+                ApiEvent::EventStreamEvent(event) => {
+                    if let Some(response) = &mut self.current_request.response {
+                        response.events.push(event);
+                    }
+                }
+                ApiEvent::EventStreamStatus(status) => {
+                    self.event_stream_status = Some(status);
+                }
+                ApiEvent::EventStreamClosed(reason) => {
+                    self.event_stream = None;
+                    self.event_stream_status = Some(reason);
+                }
+            }
+        }
+        // 응답, 업로드 진행률, 스트림 이벤트가 입력 없이도 화면에 반영되도록 한다
+        if self.is_loading || self.event_stream.is_some() || self.grpc.cancel.is_some() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+
+        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            self.render_top_panel(ui);
+        });
+
+        egui::SidePanel::left("requests_panel")
+            .resizable(true)
+            .default_width(400.0)
+            .min_width(300.0)
+            .max_width(500.0) // 최대 크기 설정
             .show(ctx, |ui| {
                 self.render_requests_panel(ui);
             });
 
+        if self.show_history {
+            egui::SidePanel::right("history_panel")
+                .resizable(true)
+                .default_width(360.0)
+                .show(ctx, |ui| {
+                    self.render_history_panel(ui);
+                });
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             self.render_main_panel(ui);
         });
 
         self.render_dialogs(ctx);
+        if self.history_error.is_none() {
+            self.history.save(&self.runtime);
+        }
     }
 }
 
@@ -581,3 +3541,223 @@ fn main() -> eframe::Result<()> {
 
     eframe::run_native("Ruquest", options, Box::new(|_cc| Ok(Box::new(app))))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn header(key: &str, value: &str, enabled: bool) -> KeyValue {
+        KeyValue {
+            enabled,
+            ..KeyValue::new(key.to_string(), value.to_string())
+        }
+    }
+
+    fn request(url: &str) -> ApiRequest {
+        ApiRequest {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn variables_prefer_request_then_group_then_environment() {
+        let environment = Environment {
+            name: "dev".to_string(),
+            variables: pairs(&[("host", "env.test"), ("id", "env"), ("token", "env")]),
+        };
+        let group = RequestGroup {
+            variables: pairs(&[("id", "group"), ("token", "group")]),
+            ..Default::default()
+        };
+        let mut req = request("https://{{host}}/{{id}}?t={{token}}");
+        req.variables = pairs(&[("token", "request"), ("", "ignored")]);
+
+        let resolved = resolve_request(&req, Some(&group), Some(&environment)).unwrap();
+        assert_eq!(resolved.url, "https://env.test/group?t=request");
+
+        let resolved = resolve_request(&req, None, Some(&environment)).unwrap();
+        assert_eq!(resolved.url, "https://env.test/env?t=request");
+
+        let Err(error) = resolve_request(&req, Some(&group), None) else {
+            panic!("host is only defined in the environment");
+        };
+        assert_eq!(error, "Unresolved variables: host");
+    }
+
+    #[test]
+    fn group_headers_are_inherited_unless_overridden() {
+        let group = RequestGroup {
+            headers: vec![
+                header("Accept", "application/json", true),
+                header("X-Group", "{{team}}", true),
+                header("X-Off", "1", false),
+            ],
+            variables: pairs(&[("team", "core")]),
+            ..Default::default()
+        };
+        let mut req = request("https://a.test/");
+        req.headers = vec![
+            header("accept", "text/plain", true),
+            header("X-Disabled", "1", false),
+        ];
+
+        let resolved = resolve_request(&req, Some(&group), None).unwrap();
+        let headers: Vec<(&str, &str)> = resolved
+            .headers
+            .iter()
+            .map(|h| (h.key.as_str(), h.value.as_str()))
+            .collect();
+        assert_eq!(headers, vec![("X-Group", "core"), ("accept", "text/plain")]);
+
+        let resolved = resolve_request(&req, None, None).unwrap();
+        assert_eq!(resolved.headers.len(), 1);
+    }
+
+    #[test]
+    fn inherited_auth_comes_from_the_group() {
+        let group = RequestGroup {
+            auth: Auth::Bearer {
+                token: "{{token}}".to_string(),
+            },
+            variables: pairs(&[("token", "group-token")]),
+            ..Default::default()
+        };
+        let req = request("https://a.test/");
+        let resolved = resolve_request(&req, Some(&group), None).unwrap();
+        assert!(
+            resolved.auth
+                == Auth::Bearer {
+                    token: "group-token".to_string()
+                }
+        );
+
+        // 그룹이 없으면 인증 없이 보낸다
+        let resolved = resolve_request(&req, None, None).unwrap();
+        assert!(resolved.auth == Auth::None);
+
+        // 요청에 지정한 인증이 우선한다
+        let mut own = request("https://a.test/");
+        own.auth = Auth::None;
+        let resolved = resolve_request(&own, Some(&group), None).unwrap();
+        assert!(resolved.auth == Auth::None);
+    }
+
+    #[test]
+    fn content_type_follows_body_type_unless_set_by_user() {
+        let content_type = |headers: &HeaderMap| {
+            headers
+                .get(CONTENT_TYPE)
+                .map(|value| value.to_str().unwrap().to_string())
+        };
+
+        let mut headers = HeaderMap::new();
+        set_content_type(&mut headers, BodyType::Json, true);
+        assert_eq!(content_type(&headers).as_deref(), Some("application/json"));
+
+        let mut headers = HeaderMap::new();
+        set_content_type(&mut headers, BodyType::FormUrlEncoded, true);
+        assert_eq!(
+            content_type(&headers).as_deref(),
+            Some("application/x-www-form-urlencoded")
+        );
+
+        // 사용자가 넣은 값은 바꾸지 않는다
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/vnd.api+json"),
+        );
+        set_content_type(&mut headers, BodyType::Json, true);
+        assert_eq!(
+            content_type(&headers).as_deref(),
+            Some("application/vnd.api+json")
+        );
+
+        // 바디가 없거나 None이면 채우지 않는다
+        let mut headers = HeaderMap::new();
+        set_content_type(&mut headers, BodyType::Json, false);
+        set_content_type(&mut headers, BodyType::None, true);
+        assert_eq!(content_type(&headers), None);
+
+        // multipart는 사용자 값을 지우고 boundary가 든 값을 나중에 붙인다
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("multipart/form-data"),
+        );
+        set_content_type(&mut headers, BodyType::FormData, true);
+        assert_eq!(content_type(&headers), None);
+    }
+
+    #[tokio::test]
+    async fn file_body_replaces_user_content_length() {
+        let path = std::env::temp_dir().join(format!("ruquest-upload-{}.bin", std::process::id()));
+        fs::write(&path, b"12345").unwrap();
+        let (tx, rx) = channel();
+
+        let request = Client::new()
+            .post("http://localhost/upload")
+            .header(CONTENT_LENGTH, "999");
+        let body = RequestBody::File(path.to_str().unwrap().to_string());
+        let request = attach_body(request, &body, &tx)
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let lengths: Vec<&HeaderValue> = request.headers().get_all(CONTENT_LENGTH).iter().collect();
+        assert_eq!(lengths, vec![&HeaderValue::from(5u64)]);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(ApiEvent::UploadProgress { sent: 0, total: 5 })
+        ));
+
+        let missing = RequestBody::File("/nonexistent/ruquest".to_string());
+        let request = Client::new().post("http://localhost/upload");
+        assert!(attach_body(request, &missing, &tx).await.is_err());
+    }
+
+    #[test]
+    fn custom_method_tokens_are_accepted() {
+        assert_eq!(parse_method("PURGE").unwrap().as_str(), "PURGE");
+        assert_eq!(parse_method("PROPFIND").unwrap().as_str(), "PROPFIND");
+        assert_eq!(parse_method("GET").unwrap(), Method::GET);
+        assert_eq!(
+            parse_method("BAD METHOD").unwrap_err(),
+            "Invalid HTTP method 'BAD METHOD'"
+        );
+        assert!(parse_method("").is_err());
+    }
+
+    #[test]
+    fn bodies_are_skipped_for_get_head_and_trace() {
+        for method in BODYLESS_METHODS {
+            let mut req = request("https://a.test/");
+            req.method = method.to_string();
+            req.body = "{\"a\": 1}".to_string();
+            let resolved = resolve_request(&req, None, None).unwrap();
+            assert_eq!(resolved.body_type, BodyType::None, "{}", method);
+            assert!(resolved.body.is_empty(), "{}", method);
+        }
+
+        for method in ["POST", "PURGE"] {
+            let mut req = request("https://a.test/");
+            req.method = method.to_string();
+            req.body = "{{payload}}".to_string();
+            req.variables = pairs(&[("payload", "{}")]);
+            let resolved = resolve_request(&req, None, None).unwrap();
+            assert_eq!(resolved.body_type, BodyType::Json, "{}", method);
+            assert_eq!(resolved.body, "{}", "{}", method);
+        }
+    }
+}
@@ -0,0 +1,638 @@
+use base64::engine::general_purpose::{URL_SAFE, URL_SAFE_NO_PAD};
+use base64::Engine;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+// 만료 전 이 시간(초) 안에 들어오면 미리 갱신
+const REFRESH_MARGIN_SECS: u64 = 30;
+const CALLBACK_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum GrantType {
+    #[default]
+    ClientCredentials,
+    Password,
+    AuthorizationCode,
+}
+
+impl GrantType {
+    pub const ALL: [GrantType; 3] = [
+        GrantType::ClientCredentials,
+        GrantType::Password,
+        GrantType::AuthorizationCode,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GrantType::ClientCredentials => "Client Credentials",
+            GrantType::Password => "Password Credentials",
+            GrantType::AuthorizationCode => "Authorization Code (PKCE)",
+        }
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OAuth2Config {
+    pub grant_type: GrantType,
+    pub token_url: String,
+    pub auth_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub username: String,
+    pub password: String,
+    pub scope: String,
+    // 0이면 빈 포트를 자동으로 사용
+    pub redirect_port: u16,
+    pub credentials_in_body: bool,
+    pub token: Option<OAuth2Token>,
+}
+
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OAuth2Token {
+    pub access_token: String,
+    pub token_type: String,
+    pub refresh_token: Option<String>,
+    pub expires_at: Option<u64>,
+    pub scope: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    token_type: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl OAuth2Token {
+    pub fn is_expired(&self, margin: u64) -> bool {
+        match self.expires_at {
+            Some(expires_at) => now_secs() + margin >= expires_at,
+            None => false,
+        }
+    }
+
+    pub fn authorization_value(&self) -> String {
+        let token_type =
+            if self.token_type.is_empty() || self.token_type.eq_ignore_ascii_case("bearer") {
+                "Bearer"
+            } else {
+                &self.token_type
+            };
+        format!("{} {}", token_type, self.access_token)
+    }
+
+    // JWT 형식이면 (header, payload)를 디코딩
+    pub fn decode_jwt(&self) -> Option<(Value, Value)> {
+        let mut parts = self.access_token.split('.');
+        let header = decode_jwt_part(parts.next()?)?;
+        let payload = decode_jwt_part(parts.next()?)?;
+        parts.next()?;
+        Some((header, payload))
+    }
+}
+
+fn decode_jwt_part(part: &str) -> Option<Value> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(part.trim_end_matches('='))
+        .or_else(|_| URL_SAFE.decode(part))
+        .ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+async fn request_token(
+    client: &Client,
+    config: &OAuth2Config,
+    mut params: Vec<(&str, String)>,
+) -> Result<OAuth2Token, String> {
+    if config.token_url.is_empty() {
+        return Err("OAuth2 token URL is empty".to_string());
+    }
+    if !config.scope.is_empty() {
+        params.push(("scope", config.scope.clone()));
+    }
+
+    let mut request = client.post(&config.token_url);
+    if config.credentials_in_body || config.client_secret.is_empty() {
+        params.push(("client_id", config.client_id.clone()));
+        if !config.client_secret.is_empty() {
+            params.push(("client_secret", config.client_secret.clone()));
+        }
+    } else {
+        request = request.basic_auth(&config.client_id, Some(&config.client_secret));
+    }
+
+    let response = request
+        .header("accept", "application/json")
+        .form(&params)
+        .send()
+        .await
+        .map_err(|e| format!("Token request failed: {}", e))?;
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        return Err(format!("Token endpoint returned {}: {}", status, body));
+    }
+
+    let parsed: TokenResponse = serde_json::from_str(&body)
+        .map_err(|e| format!("Failed to parse token response: {}", e))?;
+    Ok(OAuth2Token {
+        access_token: parsed.access_token,
+        token_type: parsed.token_type.unwrap_or_else(|| "Bearer".to_string()),
+        // 갱신 응답에 refresh_token이 없으면 기존 값을 유지
+        refresh_token: parsed
+            .refresh_token
+            .or_else(|| config.token.as_ref().and_then(|t| t.refresh_token.clone())),
+        expires_at: parsed.expires_in.map(|secs| now_secs() + secs),
+        scope: parsed.scope,
+    })
+}
+
+pub async fn fetch_token(client: &Client, config: &OAuth2Config) -> Result<OAuth2Token, String> {
+    match config.grant_type {
+        GrantType::ClientCredentials => {
+            request_token(
+                client,
+                config,
+                vec![("grant_type", "client_credentials".to_string())],
+            )
+            .await
+        }
+        GrantType::Password => {
+            request_token(
+                client,
+                config,
+                vec![
+                    ("grant_type", "password".to_string()),
+                    ("username", config.username.clone()),
+                    ("password", config.password.clone()),
+                ],
+            )
+            .await
+        }
+        GrantType::AuthorizationCode => Err(
+            "Authorization code flow requires the browser; use Get New Access Token".to_string(),
+        ),
+    }
+}
+
+pub async fn refresh_token(
+    client: &Client,
+    config: &OAuth2Config,
+    refresh_token: &str,
+) -> Result<OAuth2Token, String> {
+    request_token(
+        client,
+        config,
+        vec![
+            ("grant_type", "refresh_token".to_string()),
+            ("refresh_token", refresh_token.to_string()),
+        ],
+    )
+    .await
+}
+
+// 요청 전에 토큰을 확인한다. 새로 받은 토큰이 있으면 Some으로 반환
+pub async fn ensure_token(
+    client: &Client,
+    config: &OAuth2Config,
+) -> Result<Option<OAuth2Token>, String> {
+    match &config.token {
+        Some(token) if !token.is_expired(REFRESH_MARGIN_SECS) => Ok(None),
+        Some(OAuth2Token {
+            refresh_token: Some(refresh),
+            ..
+        }) => match refresh_token(client, config, refresh).await {
+            Ok(token) => Ok(Some(token)),
+            // refresh가 거부되면 가능한 경우 새로 발급
+            Err(e) if config.grant_type == GrantType::AuthorizationCode => Err(e),
+            Err(_) => fetch_token(client, config).await.map(Some),
+        },
+        _ => fetch_token(client, config).await.map(Some),
+    }
+}
+
+pub struct PendingAuthorization {
+    pub authorize_url: String,
+    listener: std::net::TcpListener,
+    redirect_uri: String,
+    verifier: String,
+    state: String,
+}
+
+fn random_string(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+// 루프백 리스너를 열고 브라우저로 열 인가 URL을 만든다
+pub fn begin_authorization(config: &OAuth2Config) -> Result<PendingAuthorization, String> {
+    let listener = std::net::TcpListener::bind(("127.0.0.1", config.redirect_port))
+        .map_err(|e| format!("Failed to open redirect listener: {}", e))?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("Failed to open redirect listener: {}", e))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to open redirect listener: {}", e))?
+        .port();
+    let redirect_uri = format!("http://127.0.0.1:{}/callback", port);
+
+    let verifier = random_string(64);
+    let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+    let state = random_string(32);
+
+    let mut url = reqwest::Url::parse(&config.auth_url)
+        .map_err(|e| format!("Invalid authorization URL: {}", e))?;
+    {
+        let mut query = url.query_pairs_mut();
+        query
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &config.client_id)
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("code_challenge", &challenge)
+            .append_pair("code_challenge_method", "S256")
+            .append_pair("state", &state);
+        if !config.scope.is_empty() {
+            query.append_pair("scope", &config.scope);
+        }
+    }
+
+    Ok(PendingAuthorization {
+        authorize_url: url.to_string(),
+        listener,
+        redirect_uri,
+        verifier,
+        state,
+    })
+}
+
+// 리다이렉트로 받은 code를 토큰으로 교환
+pub async fn complete_authorization(
+    client: &Client,
+    config: &OAuth2Config,
+    pending: PendingAuthorization,
+) -> Result<OAuth2Token, String> {
+    let listener = tokio::net::TcpListener::from_std(pending.listener)
+        .map_err(|e| format!("Failed to open redirect listener: {}", e))?;
+
+    let params = tokio::time::timeout(CALLBACK_TIMEOUT, async {
+        loop {
+            let (mut stream, _) = listener
+                .accept()
+                .await
+                .map_err(|e| format!("Redirect listener failed: {}", e))?;
+
+            let mut buffer = vec![0u8; 8192];
+            let read = stream.read(&mut buffer).await.unwrap_or(0);
+            let request = String::from_utf8_lossy(&buffer[..read]);
+            let target = request
+                .lines()
+                .next()
+                .and_then(|line| line.split_whitespace().nth(1))
+                .unwrap_or("/");
+
+            let url = reqwest::Url::parse(&format!("http://127.0.0.1{}", target)).ok();
+            let params: Vec<(String, String)> = url
+                .as_ref()
+                .map(|u| u.query_pairs().into_owned().collect())
+                .unwrap_or_default();
+            let is_callback = url.as_ref().is_some_and(|u| u.path() == "/callback")
+                && params.iter().any(|(k, _)| k == "code" || k == "error");
+
+            let (status, message) = if is_callback {
+                ("200 OK", "Authorization complete. You can close this window.")
+            } else {
+                ("404 Not Found", "Not found")
+            };
+            let body = format!("<html><body><p>{}</p></body></html>", message);
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+
+            if is_callback {
+                return Ok::<_, String>(params);
+            }
+        }
+    })
+    .await
+    .map_err(|_| "Timed out waiting for the authorization redirect".to_string())??;
+
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.clone())
+    };
+    if let Some(error) = param("error") {
+        return Err(format!("Authorization failed: {}", error));
+    }
+    if param("state").as_deref() != Some(pending.state.as_str()) {
+        return Err("Authorization state mismatch".to_string());
+    }
+    let code = param("code").ok_or("Authorization code missing")?;
+
+    request_token(
+        client,
+        config,
+        vec![
+            ("grant_type", "authorization_code".to_string()),
+            ("code", code),
+            ("redirect_uri", pending.redirect_uri),
+            ("code_verifier", pending.verifier),
+        ],
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::engine::general_purpose::STANDARD;
+    use std::sync::{Arc, Mutex};
+
+    // 받은 요청을 (헤더, 바디)로 모아 두고 준비된 응답을 차례로 돌려주는 로컬 토큰 엔드포인트
+    async fn token_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, Arc<Mutex<Vec<(String, String)>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/token", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = requests.clone();
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut data = Vec::new();
+                let mut buffer = [0u8; 4096];
+                let (head, length) = loop {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    data.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&data);
+                    if let Some(end) = text.find("\r\n\r\n") {
+                        let head = text[..end].to_string();
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                let (name, value) = line.split_once(':')?;
+                                name.eq_ignore_ascii_case("content-length")
+                                    .then(|| value.trim().parse::<usize>().ok())?
+                            })
+                            .unwrap_or(0);
+                        break (head, end + 4 + length);
+                    }
+                };
+                while data.len() < length {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    data.extend_from_slice(&buffer[..read]);
+                }
+                let request_body =
+                    String::from_utf8_lossy(&data[head.len() + 4..length]).into_owned();
+                received.lock().unwrap().push((head, request_body));
+
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    fn form(body: &str) -> Vec<(String, String)> {
+        reqwest::Url::parse(&format!("http://x/?{}", body))
+            .unwrap()
+            .query_pairs()
+            .into_owned()
+            .collect()
+    }
+
+    fn has(body: &str, key: &str, value: &str) -> bool {
+        form(body).iter().any(|(k, v)| k == key && v == value)
+    }
+
+    #[tokio::test]
+    async fn client_credentials_use_basic_auth() {
+        let (token_url, requests) = token_server(vec![(
+            200,
+            r#"{"access_token":"abc","expires_in":3600,"scope":"read"}"#,
+        )])
+        .await;
+        let config = OAuth2Config {
+            grant_type: GrantType::ClientCredentials,
+            token_url,
+            client_id: "id".to_string(),
+            client_secret: "secret".to_string(),
+            scope: "read".to_string(),
+            ..Default::default()
+        };
+        let token = fetch_token(&Client::new(), &config).await.unwrap();
+        assert_eq!(token.access_token, "abc");
+        assert_eq!(token.authorization_value(), "Bearer abc");
+        assert_eq!(token.scope.as_deref(), Some("read"));
+        let expires_at = token.expires_at.unwrap();
+        assert!(expires_at > now_secs() + 3500 && expires_at <= now_secs() + 3600);
+
+        let requests = requests.lock().unwrap();
+        let (head, body) = &requests[0];
+        assert!(head.starts_with("POST /token "));
+        let basic = format!("authorization: Basic {}", STANDARD.encode("id:secret"));
+        assert!(
+            head.to_lowercase().contains(&basic.to_lowercase()),
+            "{}",
+            head
+        );
+        assert!(has(body, "grant_type", "client_credentials"));
+        assert!(has(body, "scope", "read"));
+        assert!(!form(body).iter().any(|(key, _)| key == "client_secret"));
+    }
+
+    #[tokio::test]
+    async fn password_grant_sends_credentials_in_body() {
+        let (token_url, requests) =
+            token_server(vec![(200, r#"{"access_token":"p","token_type":"MAC"}"#)]).await;
+        let config = OAuth2Config {
+            grant_type: GrantType::Password,
+            token_url,
+            client_id: "id".to_string(),
+            client_secret: "s&s".to_string(),
+            username: "alice".to_string(),
+            password: "p w".to_string(),
+            credentials_in_body: true,
+            ..Default::default()
+        };
+        let token = fetch_token(&Client::new(), &config).await.unwrap();
+        assert_eq!(token.authorization_value(), "MAC p");
+        assert_eq!(token.expires_at, None);
+
+        let requests = requests.lock().unwrap();
+        let (head, body) = &requests[0];
+        assert!(!head.to_lowercase().contains("authorization:"));
+        assert!(has(body, "grant_type", "password"));
+        assert!(has(body, "username", "alice"));
+        assert!(has(body, "password", "p w"));
+        assert!(has(body, "client_id", "id"));
+        assert!(has(body, "client_secret", "s&s"));
+    }
+
+    #[tokio::test]
+    async fn ensure_token_refreshes_and_keeps_refresh_token() {
+        let (token_url, requests) =
+            token_server(vec![(200, r#"{"access_token":"new","expires_in":60}"#)]).await;
+        let config = OAuth2Config {
+            grant_type: GrantType::ClientCredentials,
+            token_url,
+            client_id: "id".to_string(),
+            token: Some(OAuth2Token {
+                access_token: "old".to_string(),
+                refresh_token: Some("r1".to_string()),
+                expires_at: Some(now_secs() + 10),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let token = ensure_token(&Client::new(), &config)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(token.access_token, "new");
+        assert_eq!(token.refresh_token.as_deref(), Some("r1"));
+        let body = requests.lock().unwrap()[0].1.clone();
+        assert!(has(&body, "grant_type", "refresh_token"));
+        assert!(has(&body, "refresh_token", "r1"));
+
+        // 만료까지 여유가 있으면 요청하지 않는다
+        let fresh = OAuth2Config {
+            token: Some(token),
+            ..config
+        };
+        assert!(ensure_token(&Client::new(), &fresh)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn rejected_refresh_falls_back_to_new_token() {
+        let (token_url, requests) = token_server(vec![
+            (400, r#"{"error":"invalid_grant"}"#),
+            (200, r#"{"access_token":"again"}"#),
+        ])
+        .await;
+        let config = OAuth2Config {
+            grant_type: GrantType::ClientCredentials,
+            token_url,
+            client_id: "id".to_string(),
+            token: Some(OAuth2Token {
+                access_token: "old".to_string(),
+                refresh_token: Some("stale".to_string()),
+                expires_at: Some(0),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let token = ensure_token(&Client::new(), &config)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(token.access_token, "again");
+        let requests = requests.lock().unwrap();
+        assert!(has(&requests[0].1, "grant_type", "refresh_token"));
+        assert!(has(&requests[1].1, "grant_type", "client_credentials"));
+    }
+
+    #[tokio::test]
+    async fn token_errors_include_status_and_body() {
+        let (token_url, _) = token_server(vec![(401, r#"{"error":"invalid_client"}"#)]).await;
+        let config = OAuth2Config {
+            grant_type: GrantType::ClientCredentials,
+            token_url,
+            ..Default::default()
+        };
+        let Err(error) = fetch_token(&Client::new(), &config).await else {
+            panic!("token request should fail");
+        };
+        assert!(error.contains("401"), "{}", error);
+        assert!(error.contains("invalid_client"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn authorization_code_flow_exchanges_code_with_verifier() {
+        let (token_url, requests) =
+            token_server(vec![(200, r#"{"access_token":"code-token"}"#)]).await;
+        let config = OAuth2Config {
+            grant_type: GrantType::AuthorizationCode,
+            token_url,
+            auth_url: "https://auth.example.com/authorize".to_string(),
+            client_id: "id".to_string(),
+            scope: "openid".to_string(),
+            ..Default::default()
+        };
+        let pending = begin_authorization(&config).unwrap();
+        let authorize = reqwest::Url::parse(&pending.authorize_url).unwrap();
+        let query: Vec<(String, String)> = authorize.query_pairs().into_owned().collect();
+        let param = |name: &str| {
+            query
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+                .unwrap()
+        };
+        assert_eq!(param("response_type"), "code");
+        assert_eq!(param("scope"), "openid");
+
+        // 브라우저 대신 리다이렉트 주소로 code를 보낸다
+        let callback = format!(
+            "{}?code=xyz&state={}",
+            param("redirect_uri"),
+            param("state")
+        );
+        let browser = tokio::spawn(async move { reqwest::get(callback).await.unwrap().status() });
+        let token = complete_authorization(&Client::new(), &config, pending)
+            .await
+            .unwrap();
+        assert_eq!(token.access_token, "code-token");
+        assert_eq!(browser.await.unwrap(), reqwest::StatusCode::OK);
+
+        let body = &requests.lock().unwrap()[0].1;
+        assert!(has(body, "grant_type", "authorization_code"));
+        assert!(has(body, "code", "xyz"));
+        let verifier = form(body)
+            .into_iter()
+            .find(|(key, _)| key == "code_verifier")
+            .unwrap()
+            .1;
+        assert_eq!(
+            URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes())),
+            param("code_challenge")
+        );
+    }
+}
@@ -0,0 +1,822 @@
+use crate::auth::{ApiKeyLocation, Auth};
+use crate::body::{encode_form, BodyType, FormPart, FormPartKind};
+use crate::import::{scalar, str_field, unique_request_name};
+use crate::oauth2::{GrantType, OAuth2Config};
+use crate::params::{url_with_params, KeyValue};
+use crate::{ApiRequest, RequestGroup};
+use serde_json::{json, Map, Value};
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+// $ref를 따라갈 최대 깊이
+const MAX_DEPTH: usize = 8;
+
+// OpenAPI 3 또는 Swagger 2 문서인지 최상위 버전 필드로 확인한다
+pub fn is_spec(value: &Value) -> bool {
+    value.get("openapi").is_some_and(Value::is_string)
+        || value.get("swagger").is_some_and(Value::is_string)
+}
+
+// 명세 하나를 그룹으로 만든다. 태그가 있는 operation은 "제목 / 태그" 그룹으로 나눈다.
+// 서버 주소는 그룹 변수 baseUrl로 두고 요청 URL은 {{baseUrl}}로 시작한다
+pub fn import(spec: &Value) -> Result<(Vec<RequestGroup>, Vec<String>), String> {
+    let swagger = spec.get("swagger").is_some();
+    if !swagger
+        && !spec
+            .get("openapi")
+            .and_then(Value::as_str)
+            .is_some_and(|version| version.starts_with('3'))
+    {
+        return Err("Only OpenAPI 3.x and Swagger 2.0 specifications are supported".to_string());
+    }
+    let title = spec
+        .pointer("/info/title")
+        .and_then(Value::as_str)
+        .unwrap_or("OpenAPI")
+        .to_string();
+
+    let mut importer = Importer {
+        spec,
+        swagger,
+        warnings: Vec::new(),
+    };
+    let auth = importer.security(spec.get("security"), &title);
+    let variables = vec![("baseUrl".to_string(), importer.base_url())];
+
+    let mut groups = vec![RequestGroup {
+        name: title.clone(),
+        variables: variables.clone(),
+        auth: auth.clone(),
+        is_expanded: true,
+        ..Default::default()
+    }];
+
+    let paths = spec.get("paths").and_then(Value::as_object);
+    for (path, item) in paths.into_iter().flatten() {
+        let item = importer.resolve(item);
+        for method in METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+            let request = importer.operation(path, method, item, operation);
+            let tag = operation
+                .pointer("/tags/0")
+                .and_then(Value::as_str)
+                .map(|tag| format!("{} / {}", title, tag));
+            let group_idx = match tag {
+                None => 0,
+                Some(name) => match groups.iter().position(|group| group.name == name) {
+                    Some(idx) => idx,
+                    None => {
+                        groups.push(RequestGroup {
+                            name,
+                            variables: variables.clone(),
+                            auth: auth.clone(),
+                            ..Default::default()
+                        });
+                        groups.len() - 1
+                    }
+                },
+            };
+            let group = &mut groups[group_idx];
+            let mut request = request;
+            request.name = unique_request_name(&group.requests, &request.name);
+            group.requests.push(request);
+        }
+    }
+
+    // 모든 operation에 태그가 있으면 비어 있는 최상위 그룹은 만들지 않는다
+    if groups.len() > 1 && groups[0].requests.is_empty() {
+        groups.remove(0);
+        groups[0].is_expanded = true;
+    }
+    Ok((groups, importer.warnings))
+}
+
+struct Importer<'a> {
+    spec: &'a Value,
+    swagger: bool,
+    warnings: Vec<String>,
+}
+
+impl<'a> Importer<'a> {
+    // 같은 문서 안의 $ref("#/components/..." 또는 "#/definitions/...")를 따라간다
+    fn resolve(&self, value: &'a Value) -> &'a Value {
+        let mut value = value;
+        for _ in 0..MAX_DEPTH {
+            let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+                break;
+            };
+            match reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.spec.pointer(pointer))
+            {
+                Some(target) => value = target,
+                None => break,
+            }
+        }
+        value
+    }
+
+    fn base_url(&mut self) -> String {
+        if self.swagger {
+            let scheme = self
+                .spec
+                .pointer("/schemes/0")
+                .and_then(Value::as_str)
+                .unwrap_or("https");
+            let host = self
+                .spec
+                .get("host")
+                .and_then(Value::as_str)
+                .unwrap_or("localhost");
+            let base_path = self
+                .spec
+                .get("basePath")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            return format!("{}://{}{}", scheme, host, base_path.trim_end_matches('/'));
+        }
+
+        let Some(server) = self.spec.pointer("/servers/0") else {
+            return String::new();
+        };
+        // 서버 변수는 기본값으로 채운다
+        let mut url = server
+            .get("url")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        if let Some(variables) = server.get("variables").and_then(Value::as_object) {
+            for (name, variable) in variables {
+                let default = variable
+                    .get("default")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                url = url.replace(&format!("{{{}}}", name), default);
+            }
+        }
+        // "/api/v1"처럼 문서 위치 기준의 상대 주소는 호스트를 알 수 없다
+        if !url.contains("://") {
+            self.warnings.push(format!(
+                "Server URL '{}' is relative; set the baseUrl variable to the full address",
+                url
+            ));
+        }
+        url.trim_end_matches('/').to_string()
+    }
+
+    fn operation(
+        &mut self,
+        path: &str,
+        method: &str,
+        item: &Value,
+        operation: &Value,
+    ) -> ApiRequest {
+        let name = ["summary", "operationId"]
+            .iter()
+            .find_map(|key| operation.get(*key).and_then(Value::as_str))
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path));
+        let mut req = ApiRequest {
+            name: name.clone(),
+            method: method.to_uppercase(),
+            ..Default::default()
+        };
+
+        // 경로 단위 파라미터에 operation 파라미터를 덮어쓴다
+        let mut parameters: Vec<&Value> = Vec::new();
+        for parameter in [item.get("parameters"), operation.get("parameters")]
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_array)
+            .flatten()
+        {
+            let parameter = self.resolve(parameter);
+            parameters.retain(|existing| {
+                existing.get("name") != parameter.get("name")
+                    || existing.get("in") != parameter.get("in")
+            });
+            parameters.push(parameter);
+        }
+
+        let mut form_fields = Vec::new();
+        for parameter in parameters {
+            let key = str_field(parameter, "name");
+            let required = parameter.get("required").and_then(Value::as_bool) == Some(true);
+            let mut entry = KeyValue::new(key.clone(), scalar(&self.parameter_example(parameter)));
+            entry.enabled = required;
+            entry.description = str_field(parameter, "description");
+
+            match parameter.get("in").and_then(Value::as_str) {
+                Some("path") => {
+                    entry.enabled = true;
+                    req.path_params.push(entry);
+                }
+                Some("query") => req.params.push(entry),
+                Some("header") => req.headers.push(entry),
+                Some("body") => {
+                    let schema = parameter.get("schema").unwrap_or(&Value::Null);
+                    req.body_type = BodyType::Json;
+                    req.body = pretty(&self.example(schema, &[]));
+                }
+                Some("formData") => form_fields.push(parameter),
+                Some(location) => self.warnings.push(format!(
+                    "'{}': {} parameter '{}' was skipped",
+                    name, location, key
+                )),
+                None => {}
+            }
+        }
+
+        // :name으로 쓸 수 없는 템플릿은 요청 변수로 옮긴다
+        let (path, variables) = path_template(path);
+        for name in variables {
+            let value = match req.path_params.iter().position(|param| param.key == name) {
+                Some(idx) => req.path_params.remove(idx).value,
+                None => String::new(),
+            };
+            req.variables.push((name, value));
+        }
+        req.url = url_with_params(&format!("{{{{baseUrl}}}}{}", path), &req.params);
+
+        if !form_fields.is_empty() {
+            self.swagger_form(&mut req, operation, &form_fields);
+        }
+        if let Some(body) = operation.get("requestBody") {
+            self.request_body(&mut req, self.resolve(body));
+        }
+        if let Some(security) = operation.get("security") {
+            req.auth = self.security(Some(security), &name);
+        }
+        req
+    }
+
+    fn parameter_example(&self, parameter: &Value) -> Value {
+        if let Some(example) = parameter.get("example") {
+            return example.clone();
+        }
+        if let Some(example) = first_example(parameter.get("examples"), self) {
+            return example;
+        }
+        match parameter.get("schema") {
+            Some(schema) => self.example(schema, &[]),
+            // Swagger 2의 파라미터는 스키마 필드를 직접 가진다
+            None if parameter.get("type").is_some() => self.example(parameter, &[]),
+            None => Value::Null,
+        }
+    }
+
+    fn request_body(&mut self, req: &mut ApiRequest, body: &Value) {
+        let Some(content) = body.get("content").and_then(Value::as_object) else {
+            return;
+        };
+        let (media_type, media) = content
+            .iter()
+            .find(|(media_type, _)| media_type.contains("json"))
+            .or_else(|| content.iter().next())
+            .map(|(media_type, media)| (media_type.as_str(), media))
+            .unwrap_or(("", &Value::Null));
+        let schema = media.get("schema").unwrap_or(&Value::Null);
+        let example = media
+            .get("example")
+            .cloned()
+            .or_else(|| first_example(media.get("examples"), self))
+            .unwrap_or_else(|| self.example(schema, &[]));
+
+        match media_type {
+            "application/x-www-form-urlencoded" => {
+                req.body_type = BodyType::FormUrlEncoded;
+                req.url_encoded = object_fields(&example)
+                    .into_iter()
+                    .map(|(key, value)| KeyValue::new(key, value))
+                    .collect();
+                let pairs: Vec<(String, String)> = req
+                    .url_encoded
+                    .iter()
+                    .map(|item| (item.key.clone(), item.value.clone()))
+                    .collect();
+                req.body = encode_form(&pairs);
+            }
+            "multipart/form-data" => {
+                req.body_type = BodyType::FormData;
+                let properties = self
+                    .resolve(schema)
+                    .get("properties")
+                    .and_then(Value::as_object);
+                req.form_data = object_fields(&example)
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let binary = properties
+                            .and_then(|properties| properties.get(&key))
+                            .map(|property| self.resolve(property))
+                            .and_then(|property| property.get("format"))
+                            .is_some_and(|format| format == "binary");
+                        if binary {
+                            FormPart {
+                                key,
+                                kind: FormPartKind::File,
+                                ..Default::default()
+                            }
+                        } else {
+                            FormPart {
+                                key,
+                                value,
+                                ..Default::default()
+                            }
+                        }
+                    })
+                    .collect();
+            }
+            "application/octet-stream" => req.body_type = BodyType::Binary,
+            media_type => {
+                req.body_type = if media_type.contains("json") {
+                    BodyType::Json
+                } else if media_type.contains("xml") {
+                    BodyType::Xml
+                } else {
+                    BodyType::Text
+                };
+                req.body = match example {
+                    Value::String(text) if !media_type.contains("json") => text,
+                    example => pretty(&example),
+                };
+                if !media_type.is_empty() && req.body_type.content_type() != Some(media_type) {
+                    req.headers.push(KeyValue::new(
+                        "Content-Type".to_string(),
+                        media_type.to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    // Swagger 2의 formData 파라미터. consumes에 multipart가 있거나 파일이 있으면 form-data로 보낸다
+    fn swagger_form(&mut self, req: &mut ApiRequest, operation: &Value, fields: &[&Value]) {
+        let consumes = operation
+            .get("consumes")
+            .or_else(|| self.spec.get("consumes"))
+            .and_then(Value::as_array);
+        let multipart = consumes.is_some_and(|consumes| {
+            consumes
+                .iter()
+                .any(|media| media.as_str() == Some("multipart/form-data"))
+        }) || fields
+            .iter()
+            .any(|field| field.get("type").and_then(Value::as_str) == Some("file"));
+
+        if multipart {
+            req.body_type = BodyType::FormData;
+            req.form_data = fields
+                .iter()
+                .map(|field| {
+                    let key = str_field(field, "name");
+                    if field.get("type").and_then(Value::as_str) == Some("file") {
+                        FormPart {
+                            key,
+                            kind: FormPartKind::File,
+                            ..Default::default()
+                        }
+                    } else {
+                        FormPart {
+                            key,
+                            value: scalar(&self.parameter_example(field)),
+                            ..Default::default()
+                        }
+                    }
+                })
+                .collect();
+        } else {
+            req.body_type = BodyType::FormUrlEncoded;
+            req.url_encoded = fields
+                .iter()
+                .map(|field| {
+                    KeyValue::new(
+                        str_field(field, "name"),
+                        scalar(&self.parameter_example(field)),
+                    )
+                })
+                .collect();
+        }
+    }
+
+    // 스키마의 example, default, enum을 우선 쓰고 없으면 타입에 맞는 값을 만든다.
+    // seen은 지금 펼치는 중인 $ref 목록으로, 자기 자신을 참조하는 스키마는 null로 끊는다
+    fn example(&self, schema: &Value, seen: &[&str]) -> Value {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            if seen.contains(&reference) || seen.len() > MAX_DEPTH {
+                return Value::Null;
+            }
+            let Some(target) = reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.spec.pointer(pointer))
+            else {
+                return Value::Null;
+            };
+            let mut seen = seen.to_vec();
+            seen.push(reference);
+            return self.example(target, &seen);
+        }
+        for key in ["example", "default"] {
+            if let Some(value) = schema.get(key) {
+                return value.clone();
+            }
+        }
+        if let Some(value) = schema.pointer("/enum/0") {
+            return value.clone();
+        }
+        if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+            let mut merged = Map::new();
+            for part in all_of {
+                if let Value::Object(fields) = self.example(part, seen) {
+                    merged.extend(fields);
+                }
+            }
+            return Value::Object(merged);
+        }
+        for key in ["oneOf", "anyOf"] {
+            if let Some(first) = schema.get(key).and_then(|schemas| schemas.get(0)) {
+                return self.example(first, seen);
+            }
+        }
+
+        let ty = match schema.get("type") {
+            Some(Value::String(ty)) => ty.as_str(),
+            // 3.1은 ["string", "null"] 같은 타입 배열을 쓴다
+            Some(Value::Array(types)) => types
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|ty| *ty != "null")
+                .unwrap_or("null"),
+            _ if schema.get("properties").is_some() => "object",
+            _ => "",
+        };
+        match ty {
+            "object" => {
+                let fields = schema
+                    .get("properties")
+                    .and_then(Value::as_object)
+                    .map(|properties| {
+                        properties
+                            .iter()
+                            .map(|(key, property)| (key.clone(), self.example(property, seen)))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Value::Object(fields)
+            }
+            "array" => match schema.get("items") {
+                Some(items) => json!([self.example(items, seen)]),
+                None => json!([]),
+            },
+            "integer" => json!(0),
+            "number" => json!(0.0),
+            "boolean" => json!(false),
+            "string" => {
+                let format = schema
+                    .get("format")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                Value::String(
+                    match format {
+                        "date-time" => "2024-01-01T00:00:00Z",
+                        "date" => "2024-01-01",
+                        "email" => "user@example.com",
+                        "uuid" => "00000000-0000-0000-0000-000000000000",
+                        "uri" | "url" => "https://example.com",
+                        "binary" | "byte" => "",
+                        _ => "string",
+                    }
+                    .to_string(),
+                )
+            }
+            _ => Value::Null,
+        }
+    }
+
+    // security 요구사항 목록의 첫 번째 방식을 인증 설정으로 바꾼다. 빈 목록은 인증 없음이다
+    fn security(&mut self, security: Option<&Value>, owner: &str) -> Auth {
+        let Some(requirements) = security.and_then(Value::as_array) else {
+            return Auth::Inherit;
+        };
+        let Some(name) = requirements
+            .iter()
+            .filter_map(Value::as_object)
+            .find_map(|requirement| requirement.keys().next())
+        else {
+            return Auth::None;
+        };
+        let pointer = if self.swagger {
+            format!("/securityDefinitions/{}", name)
+        } else {
+            format!("/components/securitySchemes/{}", name)
+        };
+        let Some(scheme) = self
+            .spec
+            .pointer(&pointer)
+            .map(|scheme| self.resolve(scheme))
+        else {
+            self.warnings.push(format!(
+                "'{}': security scheme '{}' was not found",
+                owner, name
+            ));
+            return Auth::None;
+        };
+
+        let ty = scheme
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let http_scheme = scheme
+            .get("scheme")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_lowercase();
+        match (ty, http_scheme.as_str()) {
+            ("basic", _) | ("http", "basic") => Auth::Basic {
+                username: String::new(),
+                password: String::new(),
+            },
+            ("http", "bearer") => Auth::Bearer {
+                token: String::new(),
+            },
+            ("http", "digest") => Auth::Digest {
+                username: String::new(),
+                password: String::new(),
+            },
+            ("apiKey", _) => {
+                let location = match scheme.get("in").and_then(Value::as_str) {
+                    Some("query") => ApiKeyLocation::Query,
+                    Some("header") => ApiKeyLocation::Header,
+                    location => {
+                        self.warnings.push(format!(
+                            "'{}': API key in {} is not supported, using a header instead",
+                            owner,
+                            location.unwrap_or("unknown location")
+                        ));
+                        ApiKeyLocation::Header
+                    }
+                };
+                Auth::ApiKey {
+                    key: str_field(scheme, "name"),
+                    value: String::new(),
+                    location,
+                }
+            }
+            ("oauth2", _) => Auth::OAuth2(Box::new(self.oauth2(scheme))),
+            (ty, _) => {
+                self.warnings.push(format!(
+                    "'{}': unsupported security scheme '{}' ({}) was imported as No Auth",
+                    owner, name, ty
+                ));
+                Auth::None
+            }
+        }
+    }
+
+    fn oauth2(&self, scheme: &Value) -> OAuth2Config {
+        let mut config = OAuth2Config::default();
+        let flows: Vec<(&str, &Value)> = if self.swagger {
+            let flow = match scheme.get("flow").and_then(Value::as_str) {
+                Some("password") => "password",
+                Some("accessCode") => "authorizationCode",
+                Some("application") => "clientCredentials",
+                _ => "",
+            };
+            vec![(flow, scheme)]
+        } else {
+            scheme
+                .get("flows")
+                .and_then(Value::as_object)
+                .map(|flows| {
+                    flows
+                        .iter()
+                        .map(|(name, flow)| (name.as_str(), flow))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        // Ruquest가 지원하는 흐름을 우선한다
+        let flow = ["clientCredentials", "authorizationCode", "password"]
+            .iter()
+            .find_map(|wanted| flows.iter().find(|(name, _)| name == wanted));
+        if let Some((name, flow)) = flow {
+            config.grant_type = match *name {
+                "password" => GrantType::Password,
+                "authorizationCode" => GrantType::AuthorizationCode,
+                _ => GrantType::ClientCredentials,
+            };
+            config.token_url = str_field(flow, "tokenUrl");
+            config.auth_url = str_field(flow, "authorizationUrl");
+            config.scope = flow
+                .get("scopes")
+                .and_then(Value::as_object)
+                .map(|scopes| scopes.keys().cloned().collect::<Vec<_>>().join(" "))
+                .unwrap_or_default();
+        }
+        config
+    }
+}
+
+// {id} 형식의 경로 템플릿을 :id 형식으로 바꾼다. :name은 세그먼트 맨 앞에서만 쓸 수 있으므로
+// "report-{date}.csv"처럼 중간에 있거나 이름에 다른 문자가 든 템플릿은 {{date}} 변수로 두고
+// 그 이름들을 함께 반환한다
+fn path_template(path: &str) -> (String, Vec<String>) {
+    let mut variables = Vec::new();
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| {
+            let mut output = String::new();
+            let mut rest = segment;
+            while let Some(open) = rest.find('{') {
+                let Some(close) = rest[open..].find('}').map(|close| open + close) else {
+                    break;
+                };
+                let name = &rest[open + 1..close];
+                output.push_str(&rest[..open]);
+                let after = rest[close + 1..].chars().next();
+                if output.is_empty()
+                    && !name.is_empty()
+                    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                    && !after.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    output.push_str(&format!(":{}", name));
+                } else {
+                    output.push_str(&format!("{{{{{}}}}}", name));
+                    if !variables.iter().any(|variable| variable == name) {
+                        variables.push(name.to_string());
+                    }
+                }
+                rest = &rest[close + 1..];
+            }
+            output.push_str(rest);
+            output
+        })
+        .collect();
+    (segments.join("/"), variables)
+}
+
+fn first_example(examples: Option<&Value>, importer: &Importer) -> Option<Value> {
+    let (_, example) = examples?.as_object()?.iter().next()?;
+    importer.resolve(example).get("value").cloned()
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+// 폼 바디 예제 객체를 (키, 값) 목록으로 펼친다
+fn object_fields(example: &Value) -> Vec<(String, String)> {
+    example
+        .as_object()
+        .map(|fields| {
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), scalar(value)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_template_rewrites_templates_inside_segments() {
+        assert_eq!(
+            path_template("/users/{id}/files/{name}.json"),
+            ("/users/:id/files/:name.json".to_string(), Vec::new())
+        );
+        assert_eq!(
+            path_template("/v1/{id}:cancel"),
+            ("/v1/:id:cancel".to_string(), Vec::new())
+        );
+        assert_eq!(
+            path_template("/reports/report-{date}.csv/{user-id}"),
+            (
+                "/reports/report-{{date}}.csv/{{user-id}}".to_string(),
+                vec!["date".to_string(), "user-id".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn import_builds_requests_from_openapi3() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Pets" },
+            "servers": [{ "url": "https://{env}.example.com/v1/", "variables": { "env": { "default": "api" } } }],
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": { "name": { "type": "string", "example": "Rex" } }
+                    }
+                },
+                "parameters": {
+                    "PetId": { "name": "petId", "in": "path", "required": true, "example": 7 }
+                }
+            },
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [{ "$ref": "#/components/parameters/PetId" }],
+                    "get": {
+                        "summary": "Get pet",
+                        "tags": ["pets"],
+                        "parameters": [{ "name": "verbose", "in": "query", "example": true }]
+                    },
+                    "put": {
+                        "operationId": "updatePet",
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/Pet" }
+                                }
+                            }
+                        }
+                    }
+                },
+                "/files/report-{date}.csv": {
+                    "get": {
+                        "parameters": [{ "name": "date", "in": "path", "example": "2024-01-01" }]
+                    }
+                }
+            }
+        });
+        let (groups, warnings) = import(&spec).unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(groups[0].name, "Pets");
+        assert_eq!(
+            groups[0].variables,
+            [(
+                "baseUrl".to_string(),
+                "https://api.example.com/v1".to_string()
+            )]
+        );
+
+        let find = |group: &RequestGroup, name: &str| {
+            group
+                .requests
+                .iter()
+                .find(|request| request.name == name)
+                .cloned()
+                .unwrap()
+        };
+        let update = find(&groups[0], "updatePet");
+        assert_eq!(update.name, "updatePet");
+        assert_eq!(update.url, "{{baseUrl}}/pets/:petId");
+        assert_eq!(update.path_params[0].value, "7");
+        assert_eq!(update.body_type, BodyType::Json);
+        assert_eq!(
+            serde_json::from_str::<Value>(&update.body).unwrap(),
+            json!({ "name": "Rex" })
+        );
+
+        let report = find(&groups[0], "GET /files/report-{date}.csv");
+        assert_eq!(report.url, "{{baseUrl}}/files/report-{{date}}.csv");
+        assert!(report.path_params.is_empty());
+        assert_eq!(
+            report.variables,
+            [("date".to_string(), "2024-01-01".to_string())]
+        );
+
+        assert_eq!(groups[1].name, "Pets / pets");
+        let get = find(&groups[1], "Get pet");
+        assert_eq!(get.method, "GET");
+        assert_eq!(get.url, "{{baseUrl}}/pets/:petId");
+        assert_eq!(get.params[0].key, "verbose");
+        assert_eq!(get.params[0].value, "true");
+    }
+
+    #[test]
+    fn import_warns_on_relative_server_url() {
+        let spec = json!({
+            "openapi": "3.1.0",
+            "info": { "title": "Relative" },
+            "servers": [{ "url": "/api" }],
+            "paths": {}
+        });
+        let (groups, warnings) = import(&spec).unwrap();
+        assert_eq!(groups[0].variables[0].1, "/api");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("relative"));
+    }
+
+    #[test]
+    fn import_reads_swagger2_host() {
+        let spec = json!({
+            "swagger": "2.0",
+            "info": { "title": "Old" },
+            "host": "old.example.com",
+            "basePath": "/v2/",
+            "schemes": ["http"],
+            "paths": { "/ping": { "head": {} } }
+        });
+        let (groups, _) = import(&spec).unwrap();
+        assert_eq!(groups[0].variables[0].1, "http://old.example.com/v2");
+        assert_eq!(groups[0].requests[0].method, "HEAD");
+    }
+}
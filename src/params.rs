@@ -0,0 +1,462 @@
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(from = "KeyValueRepr")]
+pub struct KeyValue {
+    pub key: String,
+    pub value: String,
+    pub enabled: bool,
+    pub description: String,
+}
+
+// 이전 버전에서 저장한 ["key", "value"] 튜플도 읽을 수 있도록 한다
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeyValueRepr {
+    Pair(String, String),
+    Entry {
+        key: String,
+        value: String,
+        #[serde(default = "default_enabled")]
+        enabled: bool,
+        #[serde(default)]
+        description: String,
+    },
+}
+
+impl From<KeyValueRepr> for KeyValue {
+    fn from(repr: KeyValueRepr) -> Self {
+        match repr {
+            KeyValueRepr::Pair(key, value) => KeyValue::new(key, value),
+            KeyValueRepr::Entry {
+                key,
+                value,
+                enabled,
+                description,
+            } => KeyValue {
+                key,
+                value,
+                enabled,
+                description,
+            },
+        }
+    }
+}
+
+impl Default for KeyValue {
+    fn default() -> Self {
+        Self::new(String::new(), String::new())
+    }
+}
+
+impl KeyValue {
+    pub fn new(key: String, value: String) -> Self {
+        Self {
+            key,
+            value,
+            enabled: true,
+            description: String::new(),
+        }
+    }
+}
+
+// URL을 (쿼리 앞부분, 쿼리) 로 나눈다. 프래그먼트는 버린다
+pub fn split_url(url: &str) -> (&str, Option<&str>) {
+    let url = url.split('#').next().unwrap_or_default();
+    match url.split_once('?') {
+        Some((base, query)) => (base, Some(query)),
+        None => (url, None),
+    }
+}
+
+// 값은 `=`가 없는 `key`면 None, `key=`면 빈 문자열이다
+pub fn parse_query(query: &str) -> Vec<(String, Option<String>)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (key.to_string(), Some(value.to_string())),
+            None => (pair.to_string(), None),
+        })
+        .collect()
+}
+
+// URL의 쿼리를 파라미터 표에 반영. 비활성 행과 설명은 유지한다
+pub fn sync_params_from_url(url: &str, params: &mut Vec<KeyValue>) {
+    let (_, query) = split_url(url);
+    let parsed = query
+        .map(parse_query)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(key, value)| (key, value.unwrap_or_default()))
+        .collect();
+    sync_key_values(parsed, params);
+}
+
+// 파싱한 키/값으로 표를 다시 채운다. 비활성 행과 설명은 유지한다
+pub fn sync_key_values(parsed: Vec<(String, String)>, params: &mut Vec<KeyValue>) {
+    let mut previous: Vec<KeyValue> = params.iter().filter(|p| p.enabled).cloned().collect();
+    let disabled: Vec<KeyValue> = params.iter().filter(|p| !p.enabled).cloned().collect();
+
+    let mut synced: Vec<KeyValue> = parsed
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (key, value))| {
+            let description = match previous.get(idx) {
+                Some(old) if old.key == key => std::mem::take(&mut previous[idx].description),
+                _ => previous
+                    .iter_mut()
+                    .find(|old| old.key == key && !old.description.is_empty())
+                    .map(|old| std::mem::take(&mut old.description))
+                    .unwrap_or_default(),
+            };
+            KeyValue {
+                description,
+                ..KeyValue::new(key, value)
+            }
+        })
+        .collect();
+    synced.extend(disabled);
+
+    *params = synced;
+}
+
+// 파라미터 표의 활성 행으로 URL의 쿼리를 다시 만든다.
+// 빈 값은 기존 URL에 `key=`로 있었으면 그대로 두고, 프래그먼트도 유지한다
+pub fn url_with_params(url: &str, params: &[KeyValue]) -> String {
+    let (base, old_query) = split_url(url);
+    let fragment = url.find('#').map(|idx| &url[idx..]).unwrap_or_default();
+    let with_equals: Vec<String> = old_query
+        .map(parse_query)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, value)| value.as_deref() == Some(""))
+        .map(|(key, _)| key)
+        .collect();
+    let query: Vec<String> = params
+        .iter()
+        .filter(|p| p.enabled && !p.key.is_empty())
+        .map(|p| {
+            if !p.value.is_empty() || with_equals.contains(&p.key) {
+                format!("{}={}", p.key, p.value)
+            } else {
+                p.key.clone()
+            }
+        })
+        .collect();
+
+    if query.is_empty() {
+        format!("{}{}", base, fragment)
+    } else {
+        format!("{}?{}{}", base, query.join("&"), fragment)
+    }
+}
+
+// 쿼리 구성요소에서 그대로 두는 문자 (unreserved와 쿼리에 허용되는 sub-delims. '+'도 그대로 둔다)
+const QUERY_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~')
+    .remove(b'!')
+    .remove(b'$')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')')
+    .remove(b'*')
+    .remove(b'+')
+    .remove(b',')
+    .remove(b';')
+    .remove(b':')
+    .remove(b'@')
+    .remove(b'/')
+    .remove(b'?');
+
+// 경로 세그먼트에서 그대로 두는 문자 ('/'는 인코딩한다)
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~')
+    .remove(b'!')
+    .remove(b'$')
+    .remove(b'&')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')')
+    .remove(b'*')
+    .remove(b'+')
+    .remove(b',')
+    .remove(b';')
+    .remove(b'=')
+    .remove(b':')
+    .remove(b'@');
+
+// 쿼리 구성요소를 퍼센트 인코딩. 이미 인코딩된 %XX는 그대로 둔다
+pub fn encode_component(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(pos) = rest.as_bytes().windows(3).position(|window| {
+        window[0] == b'%' && window[1].is_ascii_hexdigit() && window[2].is_ascii_hexdigit()
+    }) {
+        output.extend(utf8_percent_encode(&rest[..pos], QUERY_COMPONENT));
+        output.push_str(&rest[pos..pos + 3]);
+        rest = &rest[pos + 3..];
+    }
+    output.extend(utf8_percent_encode(rest, QUERY_COMPONENT));
+    output
+}
+
+// unreserved 문자만 남기고 '+', '%'까지 모두 인코딩한다. 값을 그대로 전달해야 할 때 쓴다
+const UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+pub fn encode_strict(input: &str) -> String {
+    utf8_percent_encode(input, UNRESERVED).to_string()
+}
+
+pub fn encode_query(pairs: &[(String, Option<String>)]) -> String {
+    pairs
+        .iter()
+        .map(|(key, value)| match value {
+            Some(value) => format!("{}={}", encode_component(key), encode_component(value)),
+            None => encode_component(key),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+// URL 경로에서 세그먼트 맨 앞의 `:name` 위치를 (시작, 끝, 이름)으로 반환.
+// 이름은 영문, 숫자, '_'까지이고 뒤따르는 "/:name.json"의 ".json" 같은 부분은 그대로 둔다
+fn path_param_segments(url: &str) -> Vec<(usize, usize, &str)> {
+    let (base, _) = split_url(url);
+    let path_start = match base.find("://") {
+        Some(scheme_end) => match base[scheme_end + 3..].find('/') {
+            Some(offset) => scheme_end + 3 + offset,
+            None => return Vec::new(),
+        },
+        None => base.find('/').unwrap_or(base.len()),
+    };
+
+    let mut segments = Vec::new();
+    let mut offset = path_start;
+    for segment in base[path_start..].split('/') {
+        let rest = segment.strip_prefix(':').unwrap_or_default();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        if len > 0 {
+            segments.push((offset, offset + 1 + len, &rest[..len]));
+        }
+        offset += segment.len() + 1;
+    }
+    segments
+}
+
+// URL의 `:name` 세그먼트에 맞춰 경로 파라미터 표를 갱신. 기존 값은 유지한다
+pub fn sync_path_params(url: &str, path_params: &mut Vec<KeyValue>) {
+    let mut synced: Vec<KeyValue> = Vec::new();
+    for (_, _, name) in path_param_segments(url) {
+        if synced.iter().any(|p| p.key == name) {
+            continue;
+        }
+        let param = path_params
+            .iter()
+            .find(|p| p.key == name)
+            .cloned()
+            .unwrap_or_else(|| KeyValue::new(name.to_string(), String::new()));
+        synced.push(param);
+    }
+    *path_params = synced;
+}
+
+// `:name` 세그먼트를 인코딩된 값으로 바꾼다. 값이 없는 이름은 에러로 반환
+pub fn substitute_path_params(url: &str, values: &[(String, String)]) -> Result<String, String> {
+    let mut output = String::with_capacity(url.len());
+    let mut missing = Vec::new();
+    let mut last = 0;
+
+    for (start, end, name) in path_param_segments(url) {
+        output.push_str(&url[last..start]);
+        match values.iter().find(|(key, _)| key == name) {
+            Some((_, value)) if !value.is_empty() => output.push_str(&encode_path_segment(value)),
+            _ => {
+                missing.push(name.to_string());
+                output.push_str(&url[start..end]);
+            }
+        }
+        last = end;
+    }
+    output.push_str(&url[last..]);
+
+    if missing.is_empty() {
+        Ok(output)
+    } else {
+        Err(format!("Missing path parameters: {}", missing.join(", ")))
+    }
+}
+
+pub fn encode_path_segment(input: &str) -> String {
+    utf8_percent_encode(input, PATH_SEGMENT).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_value_reads_old_pairs_and_new_entries() {
+        let items: Vec<KeyValue> = serde_json::from_str(
+            r#"[
+                ["Accept", "application/json"],
+                {"key": "X-Id", "value": "1", "enabled": false, "description": "trace"},
+                {"key": "X-New", "value": "2"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            items,
+            vec![
+                KeyValue::new("Accept".to_string(), "application/json".to_string()),
+                KeyValue {
+                    key: "X-Id".to_string(),
+                    value: "1".to_string(),
+                    enabled: false,
+                    description: "trace".to_string(),
+                },
+                KeyValue::new("X-New".to_string(), "2".to_string()),
+            ]
+        );
+
+        // 저장한 형태를 다시 읽어도 같은 값이 된다
+        let json = serde_json::to_string(&items).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<KeyValue>>(&json).unwrap(), items);
+
+        assert!(serde_json::from_str::<KeyValue>(r#"["only key"]"#).is_err());
+    }
+
+    fn params(pairs: &[(&str, &str)]) -> Vec<KeyValue> {
+        pairs
+            .iter()
+            .map(|(key, value)| KeyValue::new(key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parse_query_distinguishes_empty_values() {
+        assert_eq!(
+            parse_query("a=1&b=&c&&d=x=y"),
+            vec![
+                ("a".to_string(), Some("1".to_string())),
+                ("b".to_string(), Some(String::new())),
+                ("c".to_string(), None),
+                ("d".to_string(), Some("x=y".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn encode_query_keeps_plus_and_existing_escapes() {
+        let pairs = parse_query("q=a+b&name=%EA%B0%80 나&k=&flag");
+        assert_eq!(
+            encode_query(&pairs),
+            "q=a+b&name=%EA%B0%80%20%EB%82%98&k=&flag"
+        );
+    }
+
+    #[test]
+    fn encode_component_escapes_lone_percent_and_reserved() {
+        assert_eq!(encode_component("100%&x=#"), "100%25%26x%3D%23");
+        assert_eq!(encode_component("%2"), "%252");
+        assert_eq!(encode_path_segment("a/b c=d"), "a%2Fb%20c=d");
+    }
+
+    #[test]
+    fn url_with_params_preserves_empty_values_and_fragment() {
+        let url = "https://h/p?a=1&k=&flag#section";
+        let mut table = Vec::new();
+        sync_params_from_url(url, &mut table);
+        assert_eq!(url_with_params(url, &table), url);
+
+        table.push(KeyValue::new("new".to_string(), String::new()));
+        table[0].value = "2".to_string();
+        assert_eq!(
+            url_with_params(url, &table),
+            "https://h/p?a=2&k=&flag&new#section"
+        );
+        assert_eq!(url_with_params(url, &[]), "https://h/p#section");
+    }
+
+    #[test]
+    fn sync_keeps_disabled_rows_and_descriptions() {
+        let mut table = params(&[("a", "1"), ("b", "2")]);
+        table[0].description = "first".to_string();
+        table[1].enabled = false;
+        sync_params_from_url("https://h/?a=3&c=4", &mut table);
+        let rows: Vec<(&str, &str, bool, &str)> = table
+            .iter()
+            .map(|p| {
+                (
+                    p.key.as_str(),
+                    p.value.as_str(),
+                    p.enabled,
+                    p.description.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("a", "3", true, "first"),
+                ("c", "4", true, ""),
+                ("b", "2", false, "")
+            ]
+        );
+    }
+
+    #[test]
+    fn path_params_are_synced_and_substituted() {
+        let url = "https://h:8080/users/:id/posts/:post_id?x=:notparam";
+        let mut path = params(&[("id", "old")]);
+        sync_path_params(url, &mut path);
+        assert_eq!(path, params(&[("id", "old"), ("post_id", "")]));
+
+        let values = vec![
+            ("id".to_string(), "a b/c".to_string()),
+            ("post_id".to_string(), "7".to_string()),
+        ];
+        assert_eq!(
+            substitute_path_params("https://h:8080/users/:id/posts/:post_id", &values).unwrap(),
+            "https://h:8080/users/a%20b%2Fc/posts/7"
+        );
+        assert_eq!(
+            substitute_path_params("https://h/users/:id", &[]).unwrap_err(),
+            "Missing path parameters: id"
+        );
+    }
+
+    #[test]
+    fn path_params_stop_at_non_name_characters() {
+        let url = "https://h/files/:name.json/v1/:id:cancel/projects:list";
+        let mut path = Vec::new();
+        sync_path_params(url, &mut path);
+        assert_eq!(path, params(&[("name", ""), ("id", "")]));
+
+        let values = vec![
+            ("name".to_string(), "report".to_string()),
+            ("id".to_string(), "7".to_string()),
+        ];
+        assert_eq!(
+            substitute_path_params(url, &values).unwrap(),
+            "https://h/files/report.json/v1/7:cancel/projects:list"
+        );
+    }
+}
@@ -0,0 +1,1077 @@
+use crate::auth::{ApiKeyLocation, Auth};
+use crate::body::{decode_form, BodyType, FormPart, FormPartKind};
+use crate::import::{scalar, str_field, unique_request_name};
+use crate::oauth2::{GrantType, OAuth2Config, OAuth2Token};
+use crate::params::{split_url, sync_params_from_url, sync_path_params, KeyValue};
+use crate::sigv4::SigV4Config;
+use crate::{ApiRequest, RequestGroup, RequestKind};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+// Postman Collection v2.0/v2.1 파일인지 info.schema로 확인한다
+pub fn is_collection(value: &Value) -> bool {
+    value
+        .pointer("/info/schema")
+        .and_then(Value::as_str)
+        .is_some_and(|schema| schema.contains("schema.getpostman.com"))
+}
+
+// 컬렉션을 그룹 목록으로 바꾼다. 폴더는 "컬렉션 / 폴더" 이름의 그룹이 되고,
+// 가져올 수 없는 부분(스크립트 등)은 실패 대신 경고로 모은다
+pub fn import(collection: &Value) -> Result<(Vec<RequestGroup>, Vec<String>), String> {
+    let schema = collection
+        .pointer("/info/schema")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if !schema.contains("v2.0.0") && !schema.contains("v2.1.0") {
+        return Err(format!(
+            "Unsupported Postman collection schema '{}'",
+            schema
+        ));
+    }
+    let name = collection
+        .pointer("/info/name")
+        .and_then(Value::as_str)
+        .unwrap_or("Postman Collection")
+        .to_string();
+
+    let mut importer = Importer::default();
+    let root = RequestGroup {
+        name: name.clone(),
+        variables: variables(collection.get("variable")),
+        auth: match auth_field(collection) {
+            Some(auth) => importer.auth(auth, &name),
+            None => Auth::None,
+        },
+        is_expanded: true,
+        ..Default::default()
+    };
+    importer.warn_scripts(collection, &name);
+    importer.folder(root, children(collection));
+
+    // 요청이 하나도 없는 폴더 그룹은 만들지 않는다. 최상위 그룹은 항상 남긴다
+    let groups = importer
+        .groups
+        .into_iter()
+        .enumerate()
+        .filter(|(idx, group)| *idx == 0 || !group.requests.is_empty())
+        .map(|(_, group)| group)
+        .collect();
+    Ok((groups, importer.warnings))
+}
+
+#[derive(Default)]
+struct Importer {
+    groups: Vec<RequestGroup>,
+    warnings: Vec<String>,
+}
+
+impl Importer {
+    // 폴더 하나를 그룹으로 만들고 하위 폴더는 인증과 변수를 물려받은 그룹으로 이어 붙인다
+    fn folder(&mut self, mut group: RequestGroup, items: &[Value]) {
+        let mut subfolders = Vec::new();
+        for item in items {
+            let name = item.get("name").and_then(Value::as_str).unwrap_or_default();
+            if item.get("item").is_some() {
+                subfolders.push(item);
+            } else if let Some(request) = item.get("request") {
+                let mut request = self.request(name, request);
+                self.warn_scripts(item, name);
+                request.name = unique_request_name(&group.requests, &request.name);
+                group.requests.push(request);
+            }
+        }
+        let (parent_name, parent_auth, parent_variables) = (
+            group.name.clone(),
+            group.auth.clone(),
+            group.variables.clone(),
+        );
+        self.groups.push(group);
+
+        for folder in subfolders {
+            let name = format!(
+                "{} / {}",
+                parent_name,
+                folder
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or("Folder")
+            );
+            let auth = match auth_field(folder) {
+                Some(auth) => match self.auth(auth, &name) {
+                    Auth::Inherit => parent_auth.clone(),
+                    auth => auth,
+                },
+                None => parent_auth.clone(),
+            };
+            let mut variables = parent_variables.clone();
+            variables.extend(self::variables(folder.get("variable")));
+            self.warn_scripts(folder, &name);
+            let group = RequestGroup {
+                name,
+                variables,
+                auth,
+                is_expanded: false,
+                ..Default::default()
+            };
+            self.folder(group, children(folder));
+        }
+    }
+
+    fn request(&mut self, name: &str, request: &Value) -> ApiRequest {
+        let mut req = ApiRequest {
+            name: if name.is_empty() {
+                "Request".to_string()
+            } else {
+                name.to_string()
+            },
+            method: "GET".to_string(),
+            ..Default::default()
+        };
+
+        // v2.0에는 URL 문자열만 있는 요청도 있다
+        let request = match request {
+            Value::String(url) => {
+                req.url = url.clone();
+                sync_params_from_url(&req.url, &mut req.params);
+                sync_path_params(&req.url, &mut req.path_params);
+                return req;
+            }
+            request => request,
+        };
+
+        if let Some(method) = request.get("method").and_then(Value::as_str) {
+            req.method = method.to_uppercase();
+        }
+        self.url(&mut req, request.get("url"));
+
+        req.headers = request
+            .get("header")
+            .and_then(Value::as_array)
+            .map(|headers| headers.iter().map(key_value).collect())
+            .unwrap_or_default();
+
+        if let Some(body) = request.get("body") {
+            self.body(&mut req, body);
+        }
+        if let Some(auth) = auth_field(request) {
+            req.auth = self.auth(auth, &req.name);
+        }
+        req
+    }
+
+    fn url(&mut self, req: &mut ApiRequest, url: Option<&Value>) {
+        match url {
+            Some(Value::String(url)) => req.url = url.clone(),
+            Some(Value::Object(url)) => {
+                req.url = match url.get("raw").and_then(Value::as_str) {
+                    Some(raw) => raw.to_string(),
+                    None => build_url(url),
+                };
+                // 비활성 쿼리 파라미터는 raw에 없으므로 표에서 가져온다
+                if let Some(query) = url.get("query").and_then(Value::as_array) {
+                    req.params = query.iter().map(key_value).collect();
+                }
+                if let Some(variables) = url.get("variable").and_then(Value::as_array) {
+                    req.path_params = variables.iter().map(key_value).collect();
+                }
+            }
+            _ => {}
+        }
+        if req.params.is_empty() {
+            sync_params_from_url(&req.url, &mut req.params);
+        }
+        sync_path_params(&req.url, &mut req.path_params);
+    }
+
+    fn body(&mut self, req: &mut ApiRequest, body: &Value) {
+        if body.get("disabled").and_then(Value::as_bool) == Some(true) {
+            req.body_type = BodyType::None;
+            return;
+        }
+        let mode = body.get("mode").and_then(Value::as_str).unwrap_or_default();
+        match mode {
+            "raw" => {
+                let raw = body.get("raw").and_then(Value::as_str).unwrap_or_default();
+                let language = body
+                    .pointer("/options/raw/language")
+                    .and_then(Value::as_str);
+                req.body_type = match language {
+                    Some("json") => BodyType::Json,
+                    Some("xml") => BodyType::Xml,
+                    Some(_) => BodyType::Text,
+                    // 언어 정보가 없는 v2.0 컬렉션은 내용으로 판단한다
+                    None if serde_json::from_str::<Value>(raw).is_ok() => BodyType::Json,
+                    None => BodyType::Text,
+                };
+                req.body = raw.to_string();
+            }
+            "urlencoded" => {
+                req.body_type = BodyType::FormUrlEncoded;
+                req.url_encoded = body
+                    .get("urlencoded")
+                    .and_then(Value::as_array)
+                    .map(|items| items.iter().map(key_value).collect())
+                    .unwrap_or_default();
+            }
+            "formdata" => {
+                req.body_type = BodyType::FormData;
+                req.form_data = body
+                    .get("formdata")
+                    .and_then(Value::as_array)
+                    .map(|items| {
+                        items
+                            .iter()
+                            .map(|item| self.form_part(item, &req.name))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+            }
+            "file" => {
+                req.body_type = BodyType::Binary;
+                req.binary_path = body
+                    .pointer("/file/src")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+            }
+            "graphql" => {
+                req.kind = RequestKind::GraphQl;
+                req.graphql.query = str_field(body.get("graphql").unwrap_or(&Value::Null), "query");
+                req.graphql.variables =
+                    str_field(body.get("graphql").unwrap_or(&Value::Null), "variables");
+            }
+            "" => {}
+            mode => self.warnings.push(format!(
+                "'{}': unsupported body mode '{}' was skipped",
+                req.name, mode
+            )),
+        }
+    }
+
+    fn form_part(&mut self, item: &Value, request: &str) -> FormPart {
+        let mut part = FormPart {
+            enabled: item.get("disabled").and_then(Value::as_bool) != Some(true),
+            key: str_field(item, "key"),
+            content_type: str_field(item, "contentType"),
+            ..Default::default()
+        };
+        if item.get("type").and_then(Value::as_str) == Some("file") {
+            part.kind = FormPartKind::File;
+            // src는 문자열이거나 여러 파일의 배열이다. 첫 번째 파일만 쓴다
+            part.file_path = match item.get("src") {
+                Some(Value::String(src)) => src.clone(),
+                Some(Value::Array(srcs)) => {
+                    if srcs.len() > 1 {
+                        self.warnings.push(format!(
+                            "'{}': only the first file of form field '{}' was imported",
+                            request, part.key
+                        ));
+                    }
+                    srcs.first()
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string()
+                }
+                _ => String::new(),
+            };
+        } else {
+            part.value = str_field(item, "value");
+        }
+        part
+    }
+
+    fn auth(&mut self, auth: &Value, owner: &str) -> Auth {
+        let kind = auth.get("type").and_then(Value::as_str).unwrap_or_default();
+        let params = auth_params(auth, kind);
+        let param = |key: &str| params.get(key).cloned().unwrap_or_default();
+
+        match kind {
+            "noauth" => Auth::None,
+            "inherit" => Auth::Inherit,
+            "basic" => Auth::Basic {
+                username: param("username"),
+                password: param("password"),
+            },
+            "bearer" => Auth::Bearer {
+                token: param("token"),
+            },
+            "apikey" => Auth::ApiKey {
+                key: param("key"),
+                value: param("value"),
+                location: if param("in") == "query" {
+                    ApiKeyLocation::Query
+                } else {
+                    ApiKeyLocation::Header
+                },
+            },
+            "digest" => Auth::Digest {
+                username: param("username"),
+                password: param("password"),
+            },
+            "awsv4" => Auth::AwsSigV4(SigV4Config {
+                access_key: param("accessKey"),
+                secret_key: param("secretKey"),
+                session_token: param("sessionToken"),
+                region: param("region"),
+                service: param("service"),
+            }),
+            "oauth2" => {
+                let grant_type = match param("grant_type").as_str() {
+                    "password_credentials" => GrantType::Password,
+                    "authorization_code" | "authorization_code_with_pkce" => {
+                        GrantType::AuthorizationCode
+                    }
+                    _ => GrantType::ClientCredentials,
+                };
+                let access_token = param("accessToken");
+                Auth::OAuth2(Box::new(OAuth2Config {
+                    grant_type,
+                    token_url: param("accessTokenUrl"),
+                    auth_url: param("authUrl"),
+                    client_id: param("clientId"),
+                    client_secret: param("clientSecret"),
+                    username: param("username"),
+                    password: param("password"),
+                    scope: param("scope"),
+                    credentials_in_body: param("client_authentication") == "body",
+                    token: (!access_token.is_empty()).then(|| OAuth2Token {
+                        access_token,
+                        token_type: "Bearer".to_string(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }))
+            }
+            kind => {
+                self.warnings.push(format!(
+                    "'{}': unsupported auth type '{}' was imported as No Auth",
+                    owner, kind
+                ));
+                Auth::None
+            }
+        }
+    }
+
+    fn warn_scripts(&mut self, item: &Value, owner: &str) {
+        let scripts: Vec<&str> = item
+            .get("event")
+            .and_then(Value::as_array)
+            .map(|events| {
+                events
+                    .iter()
+                    .filter_map(|event| event.get("listen").and_then(Value::as_str))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !scripts.is_empty() {
+            self.warnings.push(format!(
+                "'{}': {} scripts were skipped",
+                owner,
+                scripts.join("/")
+            ));
+        }
+    }
+}
+
+fn children(value: &Value) -> &[Value] {
+    value
+        .get("item")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+// {key, value, disabled, description} 항목. description은 문자열이나 {content} 객체다
+fn key_value(item: &Value) -> KeyValue {
+    let description = match item.get("description") {
+        Some(Value::Object(description)) => description
+            .get("content")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        _ => str_field(item, "description"),
+    };
+    KeyValue {
+        key: str_field(item, "key"),
+        value: str_field(item, "value"),
+        enabled: item.get("disabled").and_then(Value::as_bool) != Some(true),
+        description,
+    }
+}
+
+fn variables(value: Option<&Value>) -> Vec<(String, String)> {
+    value
+        .and_then(Value::as_array)
+        .map(|variables| {
+            variables
+                .iter()
+                .filter(|variable| variable.get("disabled").and_then(Value::as_bool) != Some(true))
+                .map(|variable| {
+                    let key = match variable.get("key") {
+                        Some(_) => str_field(variable, "key"),
+                        None => str_field(variable, "id"),
+                    };
+                    (key, str_field(variable, "value"))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// v2.1은 [{key, value}] 배열, v2.0은 {key: value} 객체로 인증 값을 담는다
+// "auth": null은 Postman에서 상위 설정을 따른다는 뜻이므로 없는 것으로 본다
+fn auth_field(value: &Value) -> Option<&Value> {
+    value.get("auth").filter(|auth| !auth.is_null())
+}
+
+fn auth_params(auth: &Value, kind: &str) -> HashMap<String, String> {
+    match auth.get(kind) {
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| (str_field(item, "key"), str_field(item, "value")))
+            .collect(),
+        Some(Value::Object(params)) => params
+            .iter()
+            .map(|(key, value)| (key.clone(), scalar(value)))
+            .collect(),
+        _ => HashMap::new(),
+    }
+}
+
+// raw가 없는 URL 객체를 protocol, host, port, path, query로 조립한다
+fn build_url(url: &Map<String, Value>) -> String {
+    let join = |value: Option<&Value>, separator: &str| match value {
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .map(|part| match part {
+                Value::String(text) => text.clone(),
+                part => str_field(part, "value"),
+            })
+            .collect::<Vec<_>>()
+            .join(separator),
+        Some(Value::String(text)) => text.clone(),
+        _ => String::new(),
+    };
+
+    let mut result = String::new();
+    if let Some(protocol) = url.get("protocol").and_then(Value::as_str) {
+        result.push_str(protocol);
+        result.push_str("://");
+    }
+    result.push_str(&join(url.get("host"), "."));
+    if let Some(port) = url.get("port").and_then(Value::as_str) {
+        result.push(':');
+        result.push_str(port);
+    }
+    let path = join(url.get("path"), "/");
+    if !path.is_empty() {
+        if !path.starts_with('/') {
+            result.push('/');
+        }
+        result.push_str(&path);
+    }
+    let query: Vec<String> = url
+        .get("query")
+        .and_then(Value::as_array)
+        .map(|query| {
+            query
+                .iter()
+                .filter(|item| item.get("disabled").and_then(Value::as_bool) != Some(true))
+                .map(|item| format!("{}={}", str_field(item, "key"), str_field(item, "value")))
+                .collect()
+        })
+        .unwrap_or_default();
+    if !query.is_empty() {
+        result.push('?');
+        result.push_str(&query.join("&"));
+    }
+    result
+}
+
+const SCHEMA_V2_1: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+// 그룹을 Postman Collection v2.1로 내보낸다. Postman에 없는 요청 종류와 인증은 경고로 알린다.
+// 컬렉션에는 공통 헤더가 없으므로 그룹 헤더는 각 요청의 헤더로 옮긴다
+pub fn export(group: &RequestGroup) -> (Value, Vec<String>) {
+    let mut warnings = Vec::new();
+    let mut items = Vec::new();
+    if group.headers.iter().any(|header| !header.key.is_empty()) {
+        warnings.push(format!(
+            "'{}': group headers were copied into each request",
+            group.name
+        ));
+    }
+
+    for request in &group.requests {
+        match request.kind {
+            RequestKind::WebSocket | RequestKind::Grpc => {
+                warnings.push(format!(
+                    "'{}': {} requests are not supported by Postman collections and were skipped",
+                    request.name,
+                    request.kind.label()
+                ));
+                continue;
+            }
+            _ => {}
+        }
+        if !request.variables.is_empty() {
+            warnings.push(format!(
+                "'{}': request variables were not exported",
+                request.name
+            ));
+        }
+        items.push(json!({
+            "name": request.name,
+            "request": export_request(group, request, &mut warnings),
+        }));
+    }
+
+    let mut collection = json!({
+        "info": {
+            "name": group.name,
+            "schema": SCHEMA_V2_1,
+        },
+        "item": items,
+    });
+    if !group.variables.is_empty() {
+        collection["variable"] = group
+            .variables
+            .iter()
+            .map(|(key, value)| json!({ "key": key, "value": value }))
+            .collect();
+    }
+    if let Some(auth) = export_auth(&group.auth, &group.name, &mut warnings) {
+        collection["auth"] = auth;
+    }
+    (collection, warnings)
+}
+
+fn export_request(group: &RequestGroup, request: &ApiRequest, warnings: &mut Vec<String>) -> Value {
+    let headers: Vec<Value> = group
+        .headers
+        .iter()
+        .filter(|header| {
+            !request
+                .headers
+                .iter()
+                .any(|own| own.key.eq_ignore_ascii_case(&header.key))
+        })
+        .chain(&request.headers)
+        .filter(|header| !header.key.is_empty())
+        .map(export_key_value)
+        .collect();
+
+    let mut method = request.method.clone();
+    let body = match request.kind {
+        RequestKind::GraphQl => {
+            method = "POST".to_string();
+            Some(json!({
+                "mode": "graphql",
+                "graphql": {
+                    "query": request.graphql.query,
+                    "variables": request.graphql.variables,
+                },
+            }))
+        }
+        RequestKind::JsonRpc => {
+            // 현재 다음 id부터 매긴 JSON-RPC 바디를 그대로 내보낸다
+            method = "POST".to_string();
+            warnings.push(format!(
+                "'{}': JSON-RPC request was exported as a plain JSON POST",
+                request.name
+            ));
+            let mut jsonrpc = request.jsonrpc.clone();
+            match jsonrpc.body() {
+                Ok(body) => Some(raw_body(&body, "json")),
+                Err(e) => {
+                    warnings.push(format!("'{}': {}", request.name, e));
+                    None
+                }
+            }
+        }
+        _ => export_body(request),
+    };
+    let mut exported = json!({
+        "method": method,
+        "header": headers,
+        "url": export_url(request),
+    });
+    if let Some(body) = body {
+        exported["body"] = body;
+    }
+    if let Some(auth) = export_auth(&request.auth, &request.name, warnings) {
+        exported["auth"] = auth;
+    }
+    exported
+}
+
+fn export_key_value(item: &KeyValue) -> Value {
+    let mut value = json!({ "key": item.key, "value": item.value });
+    if !item.enabled {
+        value["disabled"] = Value::Bool(true);
+    }
+    if !item.description.is_empty() {
+        value["description"] = Value::String(item.description.clone());
+    }
+    value
+}
+
+// raw와 함께 host/path 배열도 채워 Postman이 URL을 다시 해석하지 않아도 되게 한다
+fn export_url(request: &ApiRequest) -> Value {
+    let (base, _) = split_url(&request.url);
+    let (protocol, rest) = match base.split_once("://") {
+        Some((protocol, rest)) => (Some(protocol), rest),
+        None => (None, base),
+    };
+    let (host, path) = match rest.split_once('/') {
+        Some((host, path)) => (host, Some(path)),
+        None => (rest, None),
+    };
+    let (host, port) = match host.rsplit_once(':') {
+        Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) && !port.is_empty() => {
+            (name, Some(port))
+        }
+        _ => (host, None),
+    };
+
+    let mut url = json!({
+        "raw": request.url,
+        "host": host.split('.').collect::<Vec<_>>(),
+    });
+    if let Some(protocol) = protocol {
+        url["protocol"] = Value::String(protocol.to_string());
+    }
+    if let Some(port) = port {
+        url["port"] = Value::String(port.to_string());
+    }
+    if let Some(path) = path {
+        url["path"] = path.split('/').collect::<Vec<_>>().into();
+    }
+    if !request.params.is_empty() {
+        url["query"] = request.params.iter().map(export_key_value).collect();
+    }
+    if !request.path_params.is_empty() {
+        url["variable"] = request.path_params.iter().map(export_key_value).collect();
+    }
+    url
+}
+
+fn raw_body(raw: &str, language: &str) -> Value {
+    json!({
+        "mode": "raw",
+        "raw": raw,
+        "options": { "raw": { "language": language } },
+    })
+}
+
+fn export_body(request: &ApiRequest) -> Option<Value> {
+    let body = match request.body_type {
+        BodyType::None => return None,
+        BodyType::Json | BodyType::Xml | BodyType::Text if request.body.is_empty() => return None,
+        BodyType::Json => raw_body(&request.body, "json"),
+        BodyType::Xml => raw_body(&request.body, "xml"),
+        BodyType::Text => raw_body(&request.body, "text"),
+        BodyType::FormUrlEncoded => {
+            let items: Vec<Value> = if request.url_encoded_raw {
+                decode_form(&request.body)
+                    .into_iter()
+                    .map(|(key, value)| json!({ "key": key, "value": value }))
+                    .collect()
+            } else {
+                request.url_encoded.iter().map(export_key_value).collect()
+            };
+            json!({ "mode": "urlencoded", "urlencoded": items })
+        }
+        BodyType::FormData => {
+            let parts: Vec<Value> = request
+                .form_data
+                .iter()
+                .map(|part| {
+                    let mut value = match part.kind {
+                        FormPartKind::Text => {
+                            json!({ "key": part.key, "value": part.value, "type": "text" })
+                        }
+                        FormPartKind::File => {
+                            json!({ "key": part.key, "src": part.file_path, "type": "file" })
+                        }
+                    };
+                    if !part.enabled {
+                        value["disabled"] = Value::Bool(true);
+                    }
+                    if !part.content_type.is_empty() {
+                        value["contentType"] = Value::String(part.content_type.clone());
+                    }
+                    value
+                })
+                .collect();
+            json!({ "mode": "formdata", "formdata": parts })
+        }
+        BodyType::Binary => json!({ "mode": "file", "file": { "src": request.binary_path } }),
+    };
+    Some(body)
+}
+
+// 공유용 파일에 비밀 값이 남지 않도록 {{종류_필드}} 변수로 바꾼다.
+// 비어 있거나 이미 변수인 값은 그대로 둔다
+fn redact(kind: &str, field: &str, value: &str, redacted: &mut Vec<String>) -> String {
+    let trimmed = value.trim();
+    if trimmed.is_empty() || (trimmed.starts_with("{{") && trimmed.ends_with("}}")) {
+        return value.to_string();
+    }
+    let name = format!("{}_{}", kind, field);
+    let placeholder = format!("{{{{{}}}}}", name);
+    redacted.push(name);
+    placeholder
+}
+
+// v2.1 형식의 [{key, value, type}] 인증 값. Inherit은 Postman의 기본 동작이므로 생략한다
+fn export_auth(auth: &Auth, owner: &str, warnings: &mut Vec<String>) -> Option<Value> {
+    let params = |kind: &str, values: &[(&str, &str)]| {
+        let values: Vec<Value> = values
+            .iter()
+            .map(|(key, value)| json!({ "key": key, "value": value, "type": "string" }))
+            .collect();
+        json!({ "type": kind, kind: values })
+    };
+    let mut redacted = Vec::new();
+
+    let auth = match auth {
+        Auth::Inherit => return None,
+        Auth::None => json!({ "type": "noauth" }),
+        Auth::Basic { username, password } => {
+            let password = redact("basic", "password", password, &mut redacted);
+            params("basic", &[("username", username), ("password", &password)])
+        }
+        Auth::Bearer { token } => {
+            let token = redact("bearer", "token", token, &mut redacted);
+            params("bearer", &[("token", &token)])
+        }
+        Auth::ApiKey {
+            key,
+            value,
+            location,
+        } => {
+            let location = match location {
+                ApiKeyLocation::Header => "header",
+                ApiKeyLocation::Query => "query",
+            };
+            let value = redact("apikey", "value", value, &mut redacted);
+            params(
+                "apikey",
+                &[("key", key), ("value", &value), ("in", location)],
+            )
+        }
+        Auth::Digest { username, password } => {
+            let password = redact("digest", "password", password, &mut redacted);
+            params("digest", &[("username", username), ("password", &password)])
+        }
+        Auth::AwsSigV4(config) => {
+            let secret_key = redact("awsv4", "secretKey", &config.secret_key, &mut redacted);
+            let session_token = redact(
+                "awsv4",
+                "sessionToken",
+                &config.session_token,
+                &mut redacted,
+            );
+            params(
+                "awsv4",
+                &[
+                    ("accessKey", &config.access_key),
+                    ("secretKey", &secret_key),
+                    ("sessionToken", &session_token),
+                    ("region", &config.region),
+                    ("service", &config.service),
+                ],
+            )
+        }
+        Auth::OAuth2(config) => {
+            let grant_type = match config.grant_type {
+                GrantType::ClientCredentials => "client_credentials",
+                GrantType::Password => "password_credentials",
+                GrantType::AuthorizationCode => "authorization_code_with_pkce",
+            };
+            let client_authentication = if config.credentials_in_body {
+                "body"
+            } else {
+                "header"
+            };
+            let client_secret = redact(
+                "oauth2",
+                "clientSecret",
+                &config.client_secret,
+                &mut redacted,
+            );
+            let password = redact("oauth2", "password", &config.password, &mut redacted);
+            // 발급받은 토큰은 내보내지 않는다. Postman에서 다시 발급받는다
+            if config.token.is_some() {
+                warnings.push(format!(
+                    "'{}': the OAuth 2.0 access token was not exported",
+                    owner
+                ));
+            }
+            params(
+                "oauth2",
+                &[
+                    ("grant_type", grant_type),
+                    ("accessTokenUrl", &config.token_url),
+                    ("authUrl", &config.auth_url),
+                    ("clientId", &config.client_id),
+                    ("clientSecret", &client_secret),
+                    ("username", &config.username),
+                    ("password", &password),
+                    ("scope", &config.scope),
+                    ("client_authentication", client_authentication),
+                ],
+            )
+        }
+        Auth::Hmac(_) => {
+            warnings.push(format!(
+                "'{}': HMAC signature auth is not supported by Postman and was exported as No Auth",
+                owner
+            ));
+            json!({ "type": "noauth" })
+        }
+    };
+    if !redacted.is_empty() {
+        let names: Vec<String> = redacted
+            .iter()
+            .map(|name| format!("{{{{{}}}}}", name))
+            .collect();
+        warnings.push(format!(
+            "'{}': credentials were replaced with {}; set them in a Postman environment",
+            owner,
+            names.join(", ")
+        ));
+    }
+    Some(auth)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn null_auth_inherits_from_parent() {
+        let collection = json!({
+            "info": { "name": "Shop", "schema": SCHEMA_V2_1 },
+            "auth": { "type": "bearer", "bearer": [{ "key": "token", "value": "t" }] },
+            "item": [
+                {
+                    "name": "Orders",
+                    "auth": null,
+                    "item": [{
+                        "name": "List",
+                        "request": { "method": "GET", "url": "https://x.test/orders", "auth": null }
+                    }]
+                }
+            ]
+        });
+        let (groups, warnings) = import(&collection).unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        let orders = groups
+            .iter()
+            .find(|group| group.name == "Shop / Orders")
+            .unwrap();
+        assert!(matches!(&orders.auth, Auth::Bearer { token } if token == "t"));
+        assert!(matches!(orders.requests[0].auth, Auth::Inherit));
+    }
+
+    fn header(key: &str, value: &str) -> KeyValue {
+        KeyValue::new(key.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn export_then_import_keeps_requests() {
+        let group = RequestGroup {
+            name: "Shop".to_string(),
+            variables: vec![("baseUrl".to_string(), "https://x.test".to_string())],
+            auth: Auth::Basic {
+                username: "u".to_string(),
+                password: "p".to_string(),
+            },
+            requests: vec![
+                ApiRequest {
+                    name: "Create".to_string(),
+                    method: "POST".to_string(),
+                    url: "{{baseUrl}}/items?draft=true".to_string(),
+                    params: vec![header("draft", "true")],
+                    headers: vec![header("X-Trace", "1")],
+                    body_type: BodyType::Json,
+                    body: "{\"a\":1}".to_string(),
+                    ..Default::default()
+                },
+                ApiRequest {
+                    name: "Login".to_string(),
+                    method: "POST".to_string(),
+                    url: "{{baseUrl}}/login".to_string(),
+                    body_type: BodyType::FormUrlEncoded,
+                    url_encoded: vec![header("user", "a"), header("pass", "b c")],
+                    auth: Auth::None,
+                    ..Default::default()
+                },
+                ApiRequest {
+                    name: "Upload".to_string(),
+                    method: "PUT".to_string(),
+                    url: "{{baseUrl}}/items/:id".to_string(),
+                    path_params: vec![header("id", "7")],
+                    body_type: BodyType::FormData,
+                    form_data: vec![FormPart {
+                        key: "title".to_string(),
+                        value: "hi".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let (collection, warnings) = export(&group);
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+        assert!(warnings[0].contains("{{basic_password}}"));
+        let (groups, warnings) = import(&collection).unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        let imported = &groups[0];
+
+        assert_eq!(imported.name, group.name);
+        assert_eq!(imported.variables, group.variables);
+        assert!(matches!(&imported.auth, Auth::Basic { username, password }
+            if username == "u" && password == "{{basic_password}}"));
+        assert_eq!(imported.requests.len(), group.requests.len());
+        for (imported, original) in imported.requests.iter().zip(&group.requests) {
+            assert_eq!(imported.name, original.name);
+            assert_eq!(imported.method, original.method);
+            assert_eq!(imported.url, original.url);
+            assert_eq!(imported.headers, original.headers);
+            assert_eq!(imported.params, original.params);
+            assert_eq!(imported.path_params, original.path_params);
+            assert_eq!(imported.body_type, original.body_type);
+            assert_eq!(imported.body, original.body);
+            assert_eq!(imported.url_encoded, original.url_encoded);
+            assert_eq!(
+                imported
+                    .form_data
+                    .iter()
+                    .map(|p| (&p.key, &p.value))
+                    .collect::<Vec<_>>(),
+                original
+                    .form_data
+                    .iter()
+                    .map(|p| (&p.key, &p.value))
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(
+                std::mem::discriminant(&imported.auth),
+                std::mem::discriminant(&original.auth)
+            );
+        }
+    }
+
+    #[test]
+    fn export_warns_about_folded_headers_and_jsonrpc() {
+        let group = RequestGroup {
+            name: "Rpc".to_string(),
+            headers: vec![header("X-Api-Key", "k")],
+            requests: vec![ApiRequest {
+                name: "Call".to_string(),
+                kind: RequestKind::JsonRpc,
+                url: "https://x.test/rpc".to_string(),
+                headers: vec![header("X-Own", "1")],
+                jsonrpc: crate::jsonrpc::JsonRpcRequest {
+                    calls: vec![crate::jsonrpc::JsonRpcCall {
+                        method: "sum".to_string(),
+                        params: "[1, 2]".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let (collection, warnings) = export(&group);
+        assert_eq!(warnings.len(), 2, "{:?}", warnings);
+        assert!(warnings[0].contains("group headers"));
+        assert!(warnings[1].contains("JSON-RPC"));
+        let headers = &collection["item"][0]["request"]["header"];
+        assert_eq!(headers[0]["key"], "X-Api-Key");
+        assert_eq!(headers[1]["key"], "X-Own");
+        assert_eq!(collection["item"][0]["request"]["method"], "POST");
+        let body = &collection["item"][0]["request"]["body"]["raw"];
+        assert_eq!(
+            serde_json::from_str::<Value>(body.as_str().unwrap()).unwrap(),
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "sum", "params": [1, 2] })
+        );
+    }
+
+    #[test]
+    fn export_replaces_secrets_with_placeholders() {
+        let oauth = OAuth2Config {
+            client_id: "app".to_string(),
+            client_secret: "s3cret".to_string(),
+            password: "{{my_password}}".to_string(),
+            token: Some(OAuth2Token {
+                access_token: "live-token".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let group = RequestGroup {
+            name: "Secure".to_string(),
+            auth: Auth::OAuth2(Box::new(oauth)),
+            requests: vec![ApiRequest {
+                name: "Key".to_string(),
+                url: "https://x.test/".to_string(),
+                auth: Auth::ApiKey {
+                    key: "X-Api-Key".to_string(),
+                    value: "k-123".to_string(),
+                    location: ApiKeyLocation::Header,
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let (collection, warnings) = export(&group);
+        let text = collection.to_string();
+        for secret in ["s3cret", "live-token", "k-123"] {
+            assert!(!text.contains(secret), "{} leaked", secret);
+        }
+        let value = |auth: &Value, kind: &str, key: &str| {
+            auth[kind]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|param| param["key"] == key)
+                .map(|param| param["value"].clone())
+        };
+        let auth = &collection["auth"];
+        assert_eq!(value(auth, "oauth2", "clientId"), Some(json!("app")));
+        assert_eq!(
+            value(auth, "oauth2", "clientSecret"),
+            Some(json!("{{oauth2_clientSecret}}"))
+        );
+        assert_eq!(
+            value(auth, "oauth2", "password"),
+            Some(json!("{{my_password}}"))
+        );
+        assert_eq!(value(auth, "oauth2", "accessToken"), None);
+        let auth = &collection["item"][0]["request"]["auth"];
+        assert_eq!(
+            value(auth, "apikey", "value"),
+            Some(json!("{{apikey_value}}"))
+        );
+
+        assert_eq!(warnings.len(), 3, "{:?}", warnings);
+        assert!(warnings
+            .iter()
+            .any(|w| w.contains("access token was not exported")));
+        assert!(warnings
+            .iter()
+            .any(|w| w.contains("{{oauth2_clientSecret}}")));
+        assert!(warnings
+            .iter()
+            .any(|w| w.starts_with("'Key'") && w.contains("{{apikey_value}}")));
+    }
+}
@@ -0,0 +1,172 @@
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+
+// 서버가 retry를 보내지 않았을 때 재연결 전 대기 시간(ms)
+pub const DEFAULT_RETRY_MS: u64 = 3000;
+
+#[derive(Clone, Default)]
+pub struct SseEvent {
+    pub id: Option<String>,
+    pub event: String,
+    pub data: String,
+    pub retry: Option<u64>,
+    pub timestamp: String,
+}
+
+pub fn is_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.trim_start().starts_with("text/event-stream"))
+}
+
+/// text/event-stream 파서. 청크 경계에 상관없이 줄 단위로 이벤트를 만든다.
+/// 줄 끝은 CRLF, LF, CR 모두 받는다.
+#[derive(Default)]
+pub struct Parser {
+    buffer: Vec<u8>,
+    // 직전 줄이 CR로 끝났으면 다음 청크 맨 앞의 LF는 같은 줄 끝이다
+    after_cr: bool,
+    event: String,
+    data: Vec<String>,
+    retry: Option<u64>,
+    pub last_event_id: Option<String>,
+    pub retry_ms: Option<u64>,
+}
+
+impl Parser {
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+
+        loop {
+            if self.after_cr {
+                match self.buffer.first() {
+                    Some(b'\n') => {
+                        self.buffer.remove(0);
+                    }
+                    Some(_) => {}
+                    None => break,
+                }
+                self.after_cr = false;
+            }
+            let Some(pos) = self.buffer.iter().position(|&b| b == b'\r' || b == b'\n') else {
+                break;
+            };
+            let line: Vec<u8> = self.buffer.drain(..pos).collect();
+            self.after_cr = self.buffer.remove(0) == b'\r';
+            let line = String::from_utf8_lossy(&line);
+            if let Some(event) = self.process_line(&line) {
+                events.push(event);
+            }
+        }
+
+        events
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = value.to_string(),
+            "data" => self.data.push(value.to_string()),
+            "id" if !value.contains('\0') => self.last_event_id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(retry) = value.parse() {
+                    self.retry = Some(retry);
+                    self.retry_ms = Some(retry);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    // 빈 줄을 만나면 모아둔 필드로 이벤트를 만든다. data가 없으면 버린다
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = std::mem::take(&mut self.event);
+        let retry = self.retry.take();
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(SseEvent {
+            id: self.last_event_id.clone(),
+            event: if event.is_empty() {
+                "message".to_string()
+            } else {
+                event
+            },
+            data: std::mem::take(&mut self.data).join("\n"),
+            retry,
+            timestamp: chrono::Local::now().format("%H:%M:%S%.3f").to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_chunks(chunks: &[&str]) -> (Vec<SseEvent>, Parser) {
+        let mut parser = Parser::default();
+        let events = chunks
+            .iter()
+            .flat_map(|chunk| parser.feed(chunk.as_bytes()))
+            .collect();
+        (events, parser)
+    }
+
+    #[test]
+    fn parses_fields_and_multiline_data() {
+        let (events, parser) = parse_chunks(&[
+            ": comment\nretry: 1500\nid: 7\nevent: update\ndata: a\ndata:b\n\ndata: second\n\n",
+        ]);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event, "update");
+        assert_eq!(events[0].data, "a\nb");
+        assert_eq!(events[0].id.as_deref(), Some("7"));
+        assert_eq!(events[0].retry, Some(1500));
+        assert_eq!(events[1].event, "message");
+        assert_eq!(events[1].id.as_deref(), Some("7"));
+        assert_eq!(events[1].retry, None);
+        assert_eq!(parser.retry_ms, Some(1500));
+        assert_eq!(parser.last_event_id.as_deref(), Some("7"));
+    }
+
+    #[test]
+    fn accepts_cr_lf_and_crlf_line_endings() {
+        for stream in [
+            "data: x\n\ndata: y\n\n",
+            "data: x\r\n\r\ndata: y\r\n\r\n",
+            "data: x\r\rdata: y\r\r",
+        ] {
+            let (events, _) = parse_chunks(&[stream]);
+            let data: Vec<&str> = events.iter().map(|event| event.data.as_str()).collect();
+            assert_eq!(data, ["x", "y"], "{:?}", stream);
+        }
+    }
+
+    #[test]
+    fn handles_chunk_boundaries() {
+        // CRLF가 청크 사이에서 갈려도 빈 줄로 보지 않는다
+        let (events, _) = parse_chunks(&["da", "ta: x\r", "\ndata: y\r", "\n\r", "\n"]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "x\ny");
+    }
+
+    #[test]
+    fn skips_events_without_data_and_ids_with_nul() {
+        let (events, parser) = parse_chunks(&["event: ping\n\nid: a\0b\ndata: z\n\n"]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "message");
+        assert_eq!(parser.last_event_id, None);
+    }
+}